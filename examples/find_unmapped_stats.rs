@@ -1,7 +1,7 @@
+use std::collections::BTreeSet;
+
 use espn_fantasy_football::{
-    client::EspnClient,
-    matchup::Roster,
-    team::{Team, TeamId},
+    calendar::WeekRef, client::EspnClient, player::PlayerId, stat_discovery::StatDiscovery,
 };
 
 use clap::Parser;
@@ -37,81 +37,71 @@ struct Args {
 #[tokio::main]
 async fn main() {
     let cli_args = Args::parse();
-    let week = cli_args.week;
     let swid = cli_args.swid.unwrap();
     let espn_s2 = cli_args.espn_s2.unwrap();
     let league_id = cli_args.league;
     let client = EspnClient::build(league_id, swid.as_str(), espn_s2.as_str());
     let teams = client.get_team_data(cli_args.season).await;
 
-    if cli_args.comprehensive {
-        for week in 1..=cli_args.week {
-            let data = client
-                .get_matchups_for_week(cli_args.season, week, week)
-                .await;
-
-            println!("============== WEEK {week} ==================");
-            for box_score in data {
-                let away = box_score.away.unwrap();
-                let home = box_score.home.unwrap();
-                get_unknowns(
-                    away.roster_for_current_scoring_period.unwrap(),
-                    away.team_id,
-                    &teams,
-                );
-                get_unknowns(
-                    home.roster_for_current_scoring_period.unwrap(),
-                    home.team_id,
-                    &teams,
-                );
-            }
-        }
+    let first_week = if cli_args.comprehensive {
+        1
     } else {
-        let data = client
-            .get_matchups_for_week(cli_args.season, cli_args.week, cli_args.week)
-            .await;
-
-        println!("============== WEEK {week} ==================");
-        for box_score in data {
-            let away = box_score.away.unwrap();
-            let home = box_score.home.unwrap();
-
-            get_unknowns(
-                away.roster_for_current_scoring_period.unwrap(),
-                away.team_id,
-                &teams,
-            );
-            get_unknowns(
-                home.roster_for_current_scoring_period.unwrap(),
-                home.team_id,
-                &teams,
-            );
-        }
+        cli_args.week
+    };
+    let calendar = client.get_season_calendar(cli_args.season).await;
+    let mut discovery = StatDiscovery::new();
+    for week in first_week..=cli_args.week {
+        // Look the matchup period up once rather than on every fetch.
+        let week = calendar.week(week).map_or(week.into(), WeekRef::from);
+        let data = client.get_matchups_for_week(cli_args.season, week).await;
+        discovery.add_matchups(&data);
     }
-}
-fn get_unknowns(roster: Roster, team: TeamId, team_data: &Vec<Team>) {
-    let filt = team_data
+    // Player cards add each rostered player's lines from the rest of the season.
+    let players = discovery
+        .lines()
         .iter()
-        .filter(|x| team == x.id)
+        .map(|l| l.player_id.0)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(PlayerId)
         .collect::<Vec<_>>();
-    let team_detail = filt.get(0).unwrap();
-    for player in roster.entries {
-        let stat = player
-            .player_pool_entry
-            .player
-            .stats
-            .expect("There are stats")
-            .get(0)
-            .expect("At least one")
-            .clone();
+    let cards = client.get_player_cards(cli_args.season, &players).await;
+    discovery.add_player_cards(&cards);
 
-        for val in stat.applied_stats.unwrap() {
-            if val.0.to_name() == "Unknown" && val.1 > 0.99 {
-                println!(
-                    "Team {:?} Player {} Stat Number {:?} Value {}",
-                    team_detail.name, player.player_pool_entry.player.full_name, val.0, val.1
-                )
+    for unknown in discovery.unknown_stats() {
+        println!(
+            "============== STAT {:?} ==================",
+            unknown.stat_id
+        );
+        println!(
+            "Seen {} times, {} nonzero, from {} to {}, mean {}",
+            unknown.overall.count,
+            unknown.overall.nonzero,
+            unknown.overall.min,
+            unknown.overall.max,
+            unknown.overall.mean
+        );
+        for suggestion in &unknown.suggestions {
+            println!(
+                "Moves with {} (correlation {:.2}, ratio {:?})",
+                suggestion.stat_id.to_name(),
+                suggestion.correlation,
+                suggestion.ratio
+            );
+        }
+        for (line, value) in discovery.sightings(&unknown.stat_id) {
+            if value == 0.0 {
+                continue;
             }
+            let team_name = teams
+                .iter()
+                .find(|t| Some(t.id) == line.team_id)
+                .map(|t| t.name.as_str())
+                .unwrap_or_default();
+            println!(
+                "Season {} Week {} Team {:?} Player {} Value {}",
+                line.season_id, line.scoring_period_id, team_name, line.player_name, value
+            )
         }
     }
 }
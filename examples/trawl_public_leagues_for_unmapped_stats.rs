@@ -1,20 +1,48 @@
 use std::io::Write;
 
+use async_trait::async_trait;
+use clap::Parser;
 use espn_fantasy_football::{
     client::EspnClient,
+    crawler::{Crawler, LeagueSink},
     id_maps::StatId,
-    matchup::Roster,
-    team::{Team, TeamId},
+    source::FetchError,
+    stat_discovery::{StatDiscovery, Suggestion},
 };
 use serde::{Deserialize, Serialize};
 
+#[derive(Parser)]
+struct Args {
+    #[arg(short, long, long_help = "The year of the season.")]
+    season: u16,
+}
+
 #[tokio::main]
 async fn main() {
-    find_public_league_stats(436937, 111_368_805).await;
+    let args = Args::parse();
+    let mut crawler = Crawler::public(args.season, 10.0).unwrap();
+    crawler.checkpoint = Some(format!("trawl-{}.json", args.season).into());
+    match crawler.crawl(436937..111_368_805, &UnmappedStats).await {
+        Ok(progress) => println!("{:?}", progress.tally),
+        Err(e) => println!("{}", e),
+    }
+}
+
+struct UnmappedStats;
+
+#[async_trait(?Send)]
+impl LeagueSink for UnmappedStats {
+    async fn collect(&self, client: &EspnClient, season: u16) -> Result<(), String> {
+        println!("League {} is a go!", client.league_id);
+        let data = find_unknowns_for_league(client, season)
+            .await
+            .map_err(|e| e.to_string())?;
+        write_data(client.league_id, data).map_err(|e| e.to_string())
+    }
 }
 
 fn write_data(league: i32, data: Vec<Data>) -> Result<(), std::io::Error> {
-    if data.len() > 0 {
+    if !data.is_empty() {
         let file = ::std::fs::File::create(format!("{league}.txt"))?;
         let mut file = std::io::BufWriter::new(file);
         for stat in data {
@@ -25,42 +53,41 @@ fn write_data(league: i32, data: Vec<Data>) -> Result<(), std::io::Error> {
     Ok(())
 }
 
-async fn find_unknowns_for_league(league: i32) -> Vec<Data> {
-    let mut final_data = Vec::new();
-    //println!("============== LEAGUE {league} ==================");
-
-    let client = EspnClient::build(league, "", "");
-    let teams = client.get_team_data(2023).await;
+async fn find_unknowns_for_league(
+    client: &EspnClient,
+    season: u16,
+) -> Result<Vec<Data>, FetchError> {
+    let teams = client.try_get_team_data(season).await?;
 
+    let mut discovery = StatDiscovery::new();
     for week in 1..=6 {
-        //  println!("==============  WEEK {week}  ==================");
-        let data = client.get_matchups_for_week(2023, week, week).await;
+        let data = client.try_get_matchups_for_week(season, week).await?;
+        discovery.add_matchups(&data);
+    }
 
-        for box_score in data {
-            let away = match box_score.away {
-                Some(a) => a,
-                None => break,
-            };
-            let home = match box_score.home {
-                Some(a) => a,
-                None => break,
-            };
-            match away.roster_for_current_scoring_period {
-                Some(m) => {
-                    final_data.append(&mut get_unknowns(m, away.team_id, &teams, league, week))
-                }
-                None => {}
-            };
-            match home.roster_for_current_scoring_period {
-                Some(m) => {
-                    final_data.append(&mut get_unknowns(m, home.team_id, &teams, league, week))
-                }
-                None => {}
+    let mut final_data = Vec::new();
+    for unknown in discovery.unknown_stats() {
+        for (line, value) in discovery.sightings(&unknown.stat_id) {
+            if value == 0.0 {
+                continue;
             }
+            let team_name = teams
+                .iter()
+                .find(|t| Some(t.id) == line.team_id)
+                .map(|t| t.name.clone())
+                .unwrap_or_default();
+            final_data.push(Data {
+                league: client.league_id,
+                week: line.scoring_period_id,
+                team_name,
+                player_name: line.player_name.clone(),
+                stat: unknown.stat_id.clone(),
+                value,
+                suggestions: unknown.suggestions.clone(),
+            })
         }
     }
-    //println!("============== END LEAGUE {league} ==================");
-    final_data
+    Ok(final_data)
 }
 
 #[derive(Serialize, Deserialize)]
@@ -71,74 +98,5 @@ struct Data {
     player_name: String,
     stat: StatId,
     value: f32,
-}
-
-fn get_unknowns(
-    roster: Roster,
-    team: TeamId,
-    team_data: &Vec<Team>,
-    league: i32,
-    week: u8,
-) -> Vec<Data> {
-    let team_detail = team_data.iter().find(|x| team == x.id).unwrap();
-
-    let mut return_value = Vec::new();
-    for player in roster.entries {
-        let stat = match player
-            .player_pool_entry
-            .player
-            .stats
-            .expect("There are stats")
-            .get(0)
-        {
-            Some(m) => m.clone(),
-            None => break,
-        };
-
-        for val in stat.applied_stats.unwrap() {
-            if val.0.to_name() == "Unknown" && val.1 > 0.5 {
-                // println!(
-                //     "Team {:?} Player {} Stat Number {:?} Value {}",
-                //     team_detail.name,
-                //     player.player_pool_entry.player.full_name.clone(),
-                //     val.0,
-                //     val.1
-                // );
-                return_value.push(Data {
-                    league,
-                    week,
-                    team_name: team_detail.name.clone(),
-                    stat: val.0,
-                    value: val.1,
-                    player_name: player.player_pool_entry.player.full_name.clone(),
-                })
-            }
-        }
-    }
-    return_value
-}
-
-async fn find_public_league_stats(start: i32, end: i32) {
-    for league in start..end {
-        if league % 1000 == 0 {
-            println!("Tracking at league {}", league)
-        }
-
-        let client = EspnClient::build(league, "", "");
-
-        let data = client.get_league_members(2023).await;
-        match data {
-            Ok(_) => {
-                println!("League {} is a go!", league);
-                let data = find_unknowns_for_league(league).await;
-                match write_data(league, data) {
-                    Ok(_) => {}
-                    Err(e) => println!("{}", e),
-                }
-            }
-            Err(_) => {
-                //println!("Not public");
-            }
-        };
-    }
+    suggestions: Vec<Suggestion>,
 }
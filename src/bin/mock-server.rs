@@ -0,0 +1,24 @@
+//! Serves ESPN's fantasy football API from a fixture directory.
+//!
+//! Usage: `cargo run --features mock --bin mock-server -- <fixtures dir> [address]`
+
+use std::net::SocketAddr;
+
+use espn_fantasy_football::mock::MockServer;
+
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let fixtures = args
+        .next()
+        .expect("Usage: mock-server <fixtures dir> [address]");
+    let addr: SocketAddr = args
+        .next()
+        .unwrap_or_else(|| "127.0.0.1:8080".to_string())
+        .parse()
+        .expect("Invalid address");
+    println!("Serving {} at {}", fixtures, MockServer::base_url(addr));
+    if let Err(e) = MockServer::new(fixtures).serve(addr).await {
+        panic!("Mock server failed: {e}");
+    }
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    id_maps::{ProTeamId, StatId},
+    matchup::{Matchup, MatchupKind, MatchupOutcome, RosterSlot, TeamMatchupPerformance},
+    player::{PlayerId, StatSource},
+    position::{LineupSlot, Position},
+    team::TeamId,
+};
+
+/// One matchup's scoring for a single scoring period, player by player.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BoxScore {
+    pub matchup_id: u16,
+    pub matchup_period_id: u8,
+    pub scoring_period_id: u8,
+    pub kind: MatchupKind,
+    pub winner: MatchupOutcome,
+    pub home: TeamBoxScore,
+    /// `None` for a bye.
+    pub away: Option<TeamBoxScore>,
+}
+
+impl BoxScore {
+    /// Returns `None` for a matchup without a home team. Rosters are only present when the matchup
+    /// was requested with the `mBoxscore` or `mMatchupScore` views.
+    pub fn from_matchup(matchup: &Matchup, season: u16, scoring_period_id: u8) -> Option<BoxScore> {
+        Some(BoxScore {
+            matchup_id: matchup.id,
+            matchup_period_id: matchup.matchup_period_id,
+            scoring_period_id,
+            kind: matchup.kind(),
+            winner: matchup.winner.clone(),
+            home: TeamBoxScore::from_performance(matchup.home.as_ref()?, season, scoring_period_id),
+            away: matchup
+                .away
+                .as_ref()
+                .map(|a| TeamBoxScore::from_performance(a, season, scoring_period_id)),
+        })
+    }
+
+    pub fn teams(&self) -> impl Iterator<Item = &TeamBoxScore> {
+        std::iter::once(&self.home).chain(self.away.as_ref())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TeamBoxScore {
+    pub team: TeamId,
+    /// Points scored by the starters in the scoring period.
+    pub points: f32,
+    pub projected_points: f32,
+    pub starters: Vec<BoxScorePlayer>,
+    pub bench: Vec<BoxScorePlayer>,
+}
+
+impl TeamBoxScore {
+    pub fn from_performance(
+        performance: &TeamMatchupPerformance,
+        season: u16,
+        scoring_period_id: u8,
+    ) -> TeamBoxScore {
+        let (starters, bench): (Vec<_>, Vec<_>) = performance
+            .roster_for_current_scoring_period
+            .as_ref()
+            .map(|r| {
+                r.entries
+                    .iter()
+                    .map(|e| BoxScorePlayer::from_roster_slot(e, season, scoring_period_id))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .partition(|p| p.slot.is_starter());
+        let points = performance
+            .points_by_scoring_period
+            .as_ref()
+            .and_then(|p| p.get(&(scoring_period_id as i8)).copied())
+            .unwrap_or_else(|| starters.iter().map(|p| p.points).sum());
+        TeamBoxScore {
+            team: performance.team_id,
+            points,
+            projected_points: starters.iter().filter_map(|p| p.projected_points).sum(),
+            starters,
+            bench,
+        }
+    }
+
+    /// Points left on the bench.
+    pub fn bench_points(&self) -> f32 {
+        self.bench.iter().map(|p| p.points).sum()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BoxScorePlayer {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub position: Position,
+    pub pro_team: ProTeamId,
+    pub slot: LineupSlot,
+    pub points: f32,
+    pub projected_points: Option<f32>,
+    /// Every stat the player recorded, with the fantasy points it was worth.
+    pub stats: Vec<StatBreakdown>,
+}
+
+impl BoxScorePlayer {
+    pub fn from_roster_slot(
+        entry: &RosterSlot,
+        season: u16,
+        scoring_period_id: u8,
+    ) -> BoxScorePlayer {
+        let player = &entry.player_pool_entry.player;
+        let actual = player.stat_line(StatSource::Actual, season, Some(scoring_period_id));
+        let mut stats = actual
+            .map(|s| {
+                s.stats
+                    .iter()
+                    .map(|(id, value)| {
+                        let stat = StatId(*id as u64);
+                        StatBreakdown {
+                            points: s.applied_stats.as_ref().and_then(|a| a.get(&stat).copied()),
+                            stat,
+                            value: *value,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        stats.sort_by_key(|s| s.stat.0);
+        BoxScorePlayer {
+            player_id: player.id,
+            name: player.full_name.clone(),
+            position: player.default_position_id,
+            pro_team: player.pro_team_id.clone(),
+            slot: entry.lineup_slot_id,
+            points: actual.map(|s| s.applied_total).unwrap_or_default(),
+            projected_points: player.projected_points(season, scoring_period_id),
+            stats,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct StatBreakdown {
+    pub stat: StatId,
+    pub value: f32,
+    /// `None` when the league doesn't score the stat.
+    pub points: Option<f32>,
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::*;
+    use crate::matchup::MatchupResponse;
+
+    fn week_1() -> Matchup {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/espn/ppr/2023/mMatchup+mMatchupScore_1.json");
+        let response: MatchupResponse =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        response.schedule.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn splits_starters_from_bench() {
+        let matchup = week_1();
+        let box_score = BoxScore::from_matchup(&matchup, 2023, 1).unwrap();
+        let home = &box_score.home;
+        assert_eq!(home.starters.len(), 9);
+        assert_eq!(home.bench.len(), 7);
+        assert!(home.starters.iter().all(|p| p.slot.is_starter()));
+        assert!(home.bench.iter().all(|p| p.slot == LineupSlot::Bench));
+        assert_eq!(home.points, 102.72);
+        let starter_points: f32 = home.starters.iter().map(|p| p.points).sum();
+        assert!((starter_points - home.points).abs() < 0.01);
+        assert_eq!(
+            home.bench_points(),
+            home.bench.iter().map(|p| p.points).sum::<f32>()
+        );
+        assert!(home
+            .starters
+            .iter()
+            .all(|p| p.stats.windows(2).all(|w| w[0].stat.0 < w[1].stat.0)));
+        assert_eq!(box_score.teams().count(), 2);
+    }
+
+    #[test]
+    fn byes_have_no_away_team() {
+        let mut matchup = week_1();
+        matchup.away = None;
+        let box_score = BoxScore::from_matchup(&matchup, 2023, 1).unwrap();
+        assert!(box_score.away.is_none());
+        assert_eq!(box_score.teams().count(), 1);
+
+        matchup.home = None;
+        assert!(BoxScore::from_matchup(&matchup, 2023, 1).is_none());
+    }
+
+    #[test]
+    fn missing_rosters_keep_the_team_score() {
+        let mut matchup = week_1();
+        let home = matchup.home.as_mut().unwrap();
+        home.roster_for_current_scoring_period = None;
+        let box_score = BoxScore::from_matchup(&matchup, 2023, 1).unwrap();
+        assert!(box_score.home.starters.is_empty());
+        assert!(box_score.home.bench.is_empty());
+        assert_eq!(box_score.home.points, 102.72);
+        assert_eq!(box_score.home.projected_points, 0.0);
+
+        let home = matchup.home.as_mut().unwrap();
+        home.points_by_scoring_period = None;
+        let box_score = BoxScore::from_matchup(&matchup, 2023, 1).unwrap();
+        assert_eq!(box_score.home.points, 0.0);
+        assert!(!box_score.away.unwrap().starters.is_empty());
+    }
+}
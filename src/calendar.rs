@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    league::{LeagueStatus, ScheduleSettings},
+    pro_team::ProTeam,
+    timestamp::Timestamp,
+};
+
+/// A scoring period together with the matchup period it belongs to. See `SeasonCalendar::week`.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Week {
+    pub matchup_period_id: u8,
+    pub scoring_period_id: u8,
+}
+
+impl Week {
+    pub fn new(matchup_period_id: u8, scoring_period_id: u8) -> Week {
+        Week {
+            matchup_period_id,
+            scoring_period_id,
+        }
+    }
+}
+
+/// A week as the client's getters accept it: a `Week`, or a bare scoring period.
+///
+/// Getters that pick out a matchup period look a bare scoring period up in the league's schedule,
+/// so scoring period 16 finds the second week of a two-week playoff round.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum WeekRef {
+    Week(Week),
+    ScoringPeriod(u8),
+}
+
+impl WeekRef {
+    pub fn scoring_period_id(&self) -> u8 {
+        match self {
+            WeekRef::Week(week) => week.scoring_period_id,
+            WeekRef::ScoringPeriod(scoring_period_id) => *scoring_period_id,
+        }
+    }
+
+    /// The full week, finding a bare scoring period's matchup period in the schedule. Scoring
+    /// periods the schedule doesn't list are taken to be in the matchup period of the same number.
+    pub fn resolve(&self, schedule: &ScheduleSettings) -> Week {
+        match self {
+            WeekRef::Week(week) => *week,
+            WeekRef::ScoringPeriod(scoring_period_id) => Week::new(
+                schedule
+                    .matchup_period_of(*scoring_period_id)
+                    .unwrap_or(*scoring_period_id),
+                *scoring_period_id,
+            ),
+        }
+    }
+}
+
+impl From<Week> for WeekRef {
+    fn from(week: Week) -> Self {
+        WeekRef::Week(week)
+    }
+}
+
+impl From<u8> for WeekRef {
+    fn from(scoring_period_id: u8) -> Self {
+        WeekRef::ScoringPeriod(scoring_period_id)
+    }
+}
+
+/// A scoring period on the calendar.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CalendarWeek {
+    pub week: Week,
+    pub playoff: bool,
+    /// Kickoff of the first NFL game in the scoring period, when known.
+    pub start: Option<Timestamp>,
+    /// Kickoff of the last NFL game in the scoring period, when known.
+    pub end: Option<Timestamp>,
+}
+
+/// How a league's season maps scoring periods to matchup periods and calendar dates.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SeasonCalendar {
+    pub season: u16,
+    /// Every scoring period of the season, in order.
+    pub weeks: Vec<CalendarWeek>,
+    pub current: Week,
+    /// Matchup periods after this one are playoffs.
+    pub regular_season_matchup_periods: u8,
+}
+
+impl SeasonCalendar {
+    /// Dates are left unset; see `with_dates`.
+    pub fn new(season: u16, status: &LeagueStatus, schedule: &ScheduleSettings) -> SeasonCalendar {
+        let mut weeks = schedule
+            .matchup_periods
+            .iter()
+            .flat_map(|(matchup_period_id, scoring_periods)| {
+                scoring_periods.iter().map(|sp| CalendarWeek {
+                    week: Week::new(*matchup_period_id, *sp),
+                    playoff: *matchup_period_id > schedule.matchup_period_count,
+                    start: None,
+                    end: None,
+                })
+            })
+            .collect::<Vec<_>>();
+        weeks.sort_by_key(|w| w.week.scoring_period_id);
+        SeasonCalendar {
+            season,
+            weeks,
+            current: Week::new(
+                status.current_matchup_period as u8,
+                status.latest_scoring_period as u8,
+            ),
+            regular_season_matchup_periods: schedule.matchup_period_count,
+        }
+    }
+
+    /// Fills in each scoring period's dates from the NFL schedule.
+    pub fn with_dates(mut self, pro_teams: &[ProTeam]) -> SeasonCalendar {
+        let mut dates: HashMap<u8, (Timestamp, Timestamp)> = HashMap::new();
+        let games = pro_teams
+            .iter()
+            .filter_map(|t| t.pro_games_by_scoring_period.as_ref())
+            .flat_map(|g| g.values().flatten());
+        for game in games {
+            let range = dates
+                .entry(game.scoring_period_id)
+                .or_insert((game.date, game.date));
+            range.0 = range.0.min(game.date);
+            range.1 = range.1.max(game.date);
+        }
+        for week in &mut self.weeks {
+            if let Some((start, end)) = dates.get(&week.week.scoring_period_id) {
+                week.start = Some(*start);
+                week.end = Some(*end);
+            }
+        }
+        self
+    }
+
+    /// The week a scoring period falls in.
+    pub fn week(&self, scoring_period_id: u8) -> Option<Week> {
+        self.calendar_week(scoring_period_id).map(|w| w.week)
+    }
+
+    pub fn calendar_week(&self, scoring_period_id: u8) -> Option<&CalendarWeek> {
+        self.weeks
+            .iter()
+            .find(|w| w.week.scoring_period_id == scoring_period_id)
+    }
+
+    pub fn matchup_period_of(&self, scoring_period_id: u8) -> Option<u8> {
+        self.week(scoring_period_id).map(|w| w.matchup_period_id)
+    }
+
+    /// The scoring periods that make up a matchup period, in order.
+    pub fn scoring_periods(&self, matchup_period_id: u8) -> Vec<u8> {
+        self.weeks
+            .iter()
+            .filter(|w| w.week.matchup_period_id == matchup_period_id)
+            .map(|w| w.week.scoring_period_id)
+            .collect()
+    }
+
+    pub fn is_playoff(&self, matchup_period_id: u8) -> bool {
+        matchup_period_id > self.regular_season_matchup_periods
+    }
+
+    pub fn playoff_weeks(&self) -> impl Iterator<Item = &CalendarWeek> {
+        self.weeks.iter().filter(|w| w.playoff)
+    }
+
+    /// The week being played at `time`: the first scoring period whose last game hasn't
+    /// kicked off yet. Requires dates; see `with_dates`.
+    pub fn week_at(&self, time: impl Into<Timestamp>) -> Option<Week> {
+        let time = time.into();
+        self.weeks
+            .iter()
+            .find(|w| w.end.is_some_and(|end| end >= time))
+            .map(|w| w.week)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn schedule() -> ScheduleSettings {
+        serde_json::from_str(
+            r#"{"divisions": [], "matchupPeriodCount": 14, "matchupPeriodLength": 1,
+                "matchupPeriods": {"1": [1], "2": [2], "14": [14], "15": [15, 16], "16": [17, 18]},
+                "periodTypeId": 0, "playoffMatchupPeriodLength": 2,
+                "playoffSeedingRule": "TOTAL_POINTS_SCORED", "playoffSeedingRuleBy": 0,
+                "playoffTeamCount": 4}"#,
+        )
+        .unwrap()
+    }
+
+    fn calendar() -> SeasonCalendar {
+        let status: LeagueStatus = serde_json::from_str(
+            r#"{"currentMatchupPeriod": 15, "isActive": true, "latestScoringPeriod": 16}"#,
+        )
+        .unwrap();
+        SeasonCalendar::new(2023, &status, &schedule())
+    }
+
+    #[test]
+    fn maps_scoring_periods_to_matchup_periods() {
+        let calendar = calendar();
+        assert_eq!(calendar.week(16), Some(Week::new(15, 16)));
+        assert_eq!(calendar.matchup_period_of(2), Some(2));
+        assert_eq!(calendar.scoring_periods(16), vec![17, 18]);
+        assert!(calendar.is_playoff(15));
+        assert!(!calendar.is_playoff(14));
+        assert_eq!(calendar.playoff_weeks().count(), 4);
+        assert_eq!(calendar.current, Week::new(15, 16));
+        assert_eq!(calendar.week(30), None);
+    }
+
+    #[test]
+    fn bare_scoring_periods_resolve_to_their_matchup_period() {
+        let schedule = schedule();
+        assert_eq!(WeekRef::from(16).resolve(&schedule), Week::new(15, 16));
+        assert_eq!(WeekRef::from(2).resolve(&schedule), Week::new(2, 2));
+        assert_eq!(WeekRef::from(30).resolve(&schedule), Week::new(30, 30));
+        let week = Week::new(16, 17);
+        assert_eq!(WeekRef::from(week).resolve(&schedule), week);
+        assert_eq!(WeekRef::from(week).scoring_period_id(), 17);
+    }
+}
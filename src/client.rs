@@ -1,24 +1,31 @@
+use super::box_score::BoxScore;
+use super::calendar::{SeasonCalendar, Week, WeekRef};
 use super::matchup::{Matchup, MatchupResponse};
 use super::team::{Team, TeamResponse};
+use crate::fixture::{RecordingSource, ReplaySource};
 use crate::free_agent::{FreeAgent, FreeAgentResponse};
+use crate::history::{LeagueHistory, SeasonSnapshot};
 use crate::league::{LeagueResponse, LeagueMember, LeagueStatus, LeagueSettings};
 use crate::members::MemberId;
+use crate::player::{PlayerCard, PlayerCardResponse, PlayerId};
+use crate::projection_accuracy::{samples_from_teams, AccuracyReport};
+use crate::pro_team::{opponents_for_week, ProTeam, ProTeamScheduleResponse};
+use crate::raw::Raw;
+use crate::schema::{self, SchemaReport};
 use crate::team::TeamId;
-use reqwest::StatusCode;
-use reqwest::{
-    cookie::Jar,
-    header::{HeaderMap, COOKIE},
-    Client,
-};
+use crate::trade::{Trade, TradeAnalysis, TradeAnalyzer};
+use crate::source::{DataSource, EspnRequest, FetchError, FileSource, HttpSource};
+use crate::waiver::{WaiverRecommendation, WaiverRecommender};
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
 use std::cell::RefCell;
 use std::collections::HashMap;
-const ESPN_FF_BASE_URL: &str = "https://fantasy.espn.com/apis/v3/games/ffl";
+use std::path::PathBuf;
+use std::sync::Arc;
 
 pub struct EspnClient {
-    pub client: Client,
     pub league_id: i32,
-    pub base_url: &'static str,
+    source: Arc<dyn DataSource>,
     teams: RefCell<HashMap<u16, HashMap<TeamId, Team>>>,
     members: RefCell<HashMap<u16, HashMap<MemberId, LeagueMember>>>,
 }
@@ -32,205 +39,411 @@ impl EspnClient {
     /// * swid - The ESPN SWID Cookie Value; pass an empty string if not a private league.
     /// * espn_s2 - the ESPN ESPN_S2 Cookie Value; pass an empty string if not a private league.
     pub fn build(league_id: i32, swid: &str, espn_s2: &str) -> EspnClient {
-        let mut headers = HeaderMap::new();
-        let cookie_store = Jar::default();
-        if swid != "" && espn_s2 != "" {
-            headers.insert(COOKIE, format!("SWID={swid}").parse().unwrap());
-            headers.insert(COOKIE, format!("espn_s2={espn_s2}").parse().unwrap());
-            cookie_store.add_cookie_str(
-                format!("SWID={swid}; espn_s2={espn_s2}").as_str(),
-                &ESPN_FF_BASE_URL.parse().unwrap(),
-            );
-        }
-        let client_builder = Client::builder()
-            .default_headers(headers)
-            .cookie_store(true)
-            .cookie_provider(cookie_store.into())
-            .build();
-        // let client = match client_builder {
-        //     Ok(c) => ClientBuilder::new(c)
-        //         .with(Cache(HttpCache {
-        //             mode: CacheMode::Default,
-        //             manager: CACacheManager::default(),
-        //             options: HttpCacheOptions::default(),
-        //         }))
-        //         .build(),
-        //     Err(e) => panic!("Failed to construct client. Aborting. {e}"),
-        // };
-        let client = match client_builder {
-            Ok(c) => c,
-            Err(e) => panic!("Failed to construct client. Aborting. {e}"),
-        };
+        EspnClient::with_source(league_id, Arc::new(HttpSource::new(swid, espn_s2)))
+    }
+
+    /// Build an EspnClient that reads responses saved to `dir` instead of calling ESPN.
+    ///
+    /// See `FileSource` for how the directory is laid out.
+    pub fn offline(league_id: i32, dir: impl Into<PathBuf>) -> EspnClient {
+        EspnClient::with_source(league_id, Arc::new(FileSource::new(dir)))
+    }
+
+    /// Build an EspnClient that calls ESPN and saves every response to `dir` for `replay`.
+    pub fn recording(
+        league_id: i32,
+        swid: &str,
+        espn_s2: &str,
+        dir: impl Into<PathBuf>,
+    ) -> EspnClient {
+        let source = RecordingSource::new(HttpSource::new(swid, espn_s2), dir);
+        EspnClient::with_source(league_id, Arc::new(source))
+    }
+
+    /// Build an EspnClient that serves responses saved by `recording`, panicking on any
+    /// request that wasn't recorded.
+    pub fn replay(league_id: i32, dir: impl Into<PathBuf>) -> EspnClient {
+        EspnClient::with_source(league_id, Arc::new(ReplaySource::new(dir)))
+    }
+
+    /// Build an EspnClient that gets its data from any source.
+    pub fn with_source(league_id: i32, source: Arc<dyn DataSource>) -> EspnClient {
         EspnClient {
-            client,
             league_id,
-            base_url: ESPN_FF_BASE_URL,
+            source,
             teams: RefCell::new(HashMap::new()),
             members: RefCell::new(HashMap::new()),
         }
     }
 
+    pub fn source(&self) -> &Arc<dyn DataSource> {
+        &self.source
+    }
+
+    /// The HTTP client requests go through, unless the client reads from disk.
+    pub fn client(&self) -> Option<&reqwest::Client> {
+        self.source.http().map(|h| &h.client)
+    }
+
+    /// The URL requests are sent to, unless the client reads from disk.
+    pub fn base_url(&self) -> Option<&str> {
+        self.source.http().map(|h| h.base_url.as_str())
+    }
+
+    /// A request for this league's data in a season. Add views to it and pass it to `get_raw`.
+    pub fn league_request(&self, season: u16) -> EspnRequest {
+        EspnRequest::league(season, self.league_id)
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, request: &EspnRequest) -> Result<T, FetchError> {
+        let body = self.source.fetch(request).await?;
+        let de = &mut serde_json::Deserializer::from_str(&body);
+        serde_path_to_error::deserialize(de)
+            .map_err(|e| FetchError::Deserialize(format!("{} at `{}`", e.inner(), e.path())))
+    }
+
+    /// Fetch a request and keep the JSON alongside the struct it's read into, for reaching fields
+    /// the struct doesn't model. Build the request with `league_request`, `free_agent_request`
+    /// or `player_card_request`.
+    pub async fn get_raw<T: DeserializeOwned>(
+        &self,
+        request: &EspnRequest,
+    ) -> Result<Raw<T>, FetchError> {
+        self.fetch(request).await
+    }
+
+    /// Fetch a request and compare the response with the struct it's read into.
+    pub async fn schema_report<T: DeserializeOwned + Serialize>(
+        &self,
+        request: &EspnRequest,
+    ) -> SchemaReport {
+        match self.source.fetch(request).await {
+            Ok(body) => schema::check::<T>(request.to_string(), &body).1,
+            Err(e) => SchemaReport {
+                error: Some(e.to_string()),
+                ..SchemaReport::new::<T>(request.to_string())
+            },
+        }
+    }
+
+    /// Compare every view this client reads with the structs it's read into, to find fields ESPN
+    /// has added or stopped sending.
+    pub async fn check_schema(&self, season: u16, week: impl Into<WeekRef>) -> Vec<SchemaReport> {
+        let sp = week.into().scoring_period_id();
+        let league = || self.league_request(season);
+        vec![
+            self.schema_report::<LeagueResponse>(&league().view("mStatus"))
+                .await,
+            self.schema_report::<LeagueResponse>(&league().view("mSettings"))
+                .await,
+            self.schema_report::<TeamResponse>(&league().view("mTeam"))
+                .await,
+            self.schema_report::<TeamResponse>(
+                &league().view("mTeam").view("mRoster").scoring_period(sp),
+            )
+            .await,
+            self.schema_report::<MatchupResponse>(&league().view("mMatchup"))
+                .await,
+            self.schema_report::<MatchupResponse>(
+                &league()
+                    .view("mMatchup")
+                    .view("mMatchupScore")
+                    .scoring_period(sp),
+            )
+            .await,
+            self.schema_report::<MatchupResponse>(
+                &league()
+                    .view("mBoxscore")
+                    .view("mMatchupScore")
+                    .view("mScoreboard")
+                    .scoring_period(sp),
+            )
+            .await,
+            self.schema_report::<FreeAgentResponse>(&self.free_agent_request(season, sp, 50))
+                .await,
+            self.schema_report::<ProTeamScheduleResponse>(
+                &EspnRequest::season(season).view("proTeamSchedules_wl"),
+            )
+            .await,
+        ]
+    }
+
     pub async fn get_league_members(&self, season: u16) -> Result<Vec<LeagueMember>, String> {
-        let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[("view", "mTeam")]);
-        let res = req.send().await.expect("LeagueInfoResponse");
-        let result = match res.status() {
-            StatusCode::UNAUTHORIZED => Err("401".to_string()),
-            StatusCode::OK => {
-                let data = match res.json::<LeagueResponse>().await {
-                    Ok(f) => f,
-                    Err(e) => {
-                        return Err(format!(
-                            "LeagueInfoResponse Deserialization Error; Ignoring. {}",
-                            e
-                        ))
-                    }
-                };
-                match data.members {
-                    Some(m) => Ok(m),
-                    None => return Err("No league members, but there should be".to_string()),
-                }
-            }
-            StatusCode::NOT_FOUND => Err("404".to_string()),
-            _ => {
-                dbg!(res);
-                Err("Unknown Error".to_string())
+        let req = self.league_request(season).view("mTeam");
+        let data = match self.fetch::<LeagueResponse>(&req).await {
+            Ok(f) => f,
+            Err(FetchError::Deserialize(e)) => {
+                return Err(format!(
+                    "LeagueInfoResponse Deserialization Error; Ignoring. {}",
+                    e
+                ))
             }
+            Err(e) => return Err(e.to_string()),
         };
-        result
+        match data.members {
+            Some(m) => Ok(m),
+            None => Err("No league members, but there should be".to_string()),
+        }
     }
     pub async fn get_league_status(&self, season: u16) -> LeagueStatus {
-        let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[("view", "mStatus")]);
-        let res = req.send().await.expect("LeagueInfoResponse");
-        let data = res
-            .json::<LeagueResponse>()
+        self.try_get_league_status(season)
             .await
-            .expect("LeagueInfoResponse Deserialization");
-        match data.status {
-            Some(s) => s,
-            None => panic!("No league status, but there should be"),
-        }
+            .expect("LeagueInfoResponse Deserialization")
+    }
+
+    /// Like `get_league_status`, but returns failures instead of panicking.
+    pub async fn try_get_league_status(&self, season: u16) -> Result<LeagueStatus, FetchError> {
+        let req = self.league_request(season).view("mStatus");
+        let data = self.fetch::<LeagueResponse>(&req).await?;
+        data.status.ok_or_else(|| {
+            FetchError::Deserialize("No league status, but there should be".to_string())
+        })
     }
 
     pub async fn get_league_settings(&self, season: u16) -> LeagueSettings {
-        let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[("view", "mSettings")]);
-        let res = req.send().await.expect("LeagueSettingsResponse");
-        let data = res
-            .json::<LeagueResponse>()
+        self.try_get_league_settings(season)
             .await
-            .expect("LeagueSettingsResponse Deserialization");
-        match data.settings {
-            Some(s) => s,
-            None => panic!("No league settings found, but there should be"),
-        }
+            .expect("LeagueSettingsResponse Deserialization")
+    }
+
+    /// Like `get_league_settings`, but returns failures instead of panicking.
+    pub async fn try_get_league_settings(&self, season: u16) -> Result<LeagueSettings, FetchError> {
+        let req = self.league_request(season).view("mSettings");
+        let data = self.fetch::<LeagueResponse>(&req).await?;
+        data.settings.ok_or_else(|| {
+            FetchError::Deserialize("No league settings found, but there should be".to_string())
+        })
     }
 
     pub async fn get_team_data(&self, season: u16) -> Vec<Team> {
-        let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[("view", "mTeam")]);
-        let res = req.send().await.expect("Team ");
-
-        let data = res
-            .json::<TeamResponse>()
+        self.try_get_team_data(season)
+            .await
+            .expect("TeamResponse Deserialization")
+    }
+
+    /// Like `get_team_data`, but returns failures instead of panicking.
+    pub async fn try_get_team_data(&self, season: u16) -> Result<Vec<Team>, FetchError> {
+        let req = self.league_request(season).view("mTeam");
+        Ok(self.fetch::<TeamResponse>(&req).await?.teams)
+    }
+
+    pub async fn get_teams_at_week(&self, season: u16, week: impl Into<WeekRef>) -> Vec<Team> {
+        self.try_get_teams_at_week(season, week)
             .await
-            .expect("TeamResponse Deserialization");
-        data.teams
+            .expect("TeamResponse Deserialization")
     }
 
-    pub async fn get_teams_at_week(&self, season: u16, scoring_period_id: u8) -> Vec<Team> {
+    /// Like `get_teams_at_week`, but returns failures instead of panicking.
+    pub async fn try_get_teams_at_week(
+        &self,
+        season: u16,
+        week: impl Into<WeekRef>,
+    ) -> Result<Vec<Team>, FetchError> {
+        let scoring_period_id = week.into().scoring_period_id();
         let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[
-                ("view", "mTeam"),
-                ("view", "mRoster"),
-                ("scoringPeriodId", &scoring_period_id.to_string()),
-            ]);
-        let res = req.send().await.expect("Team ");
-
-        let data = res
-            .json::<TeamResponse>()
+            .league_request(season)
+            .view("mTeam")
+            .view("mRoster")
+            .scoring_period(scoring_period_id);
+        Ok(self.fetch::<TeamResponse>(&req).await?.teams)
+    }
+
+    /// The full week for a bare scoring period, from the league's schedule settings.
+    async fn resolve_week(&self, season: u16, week: WeekRef) -> Week {
+        self.try_resolve_week(season, week)
             .await
-            .expect("TeamResponse Deserialization");
-        data.teams
+            .expect("LeagueSettingsResponse Deserialization")
+    }
+
+    async fn try_resolve_week(&self, season: u16, week: WeekRef) -> Result<Week, FetchError> {
+        match week {
+            WeekRef::Week(week) => Ok(week),
+            WeekRef::ScoringPeriod(_) => {
+                let settings = self.try_get_league_settings(season).await?;
+                Ok(week.resolve(&settings.schedule_settings))
+            }
+        }
     }
 
     /// Get data about all matchups for the season.
     ///
     /// Does not include rosters.
     pub async fn get_matchups(&self, season: u16) -> Vec<Matchup> {
-        let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[("view", "mMatchup")]);
-        let res = req.send().await.expect("LeagueSettingsResponse");
-        let data = res
-            .json::<MatchupResponse>()
+        self.try_get_matchups(season)
             .await
-            .expect("MatchupResponse Deserialization");
-        data.schedule
+            .expect("MatchupResponse Deserialization")
+    }
+
+    /// Like `get_matchups`, but returns failures instead of panicking.
+    pub async fn try_get_matchups(&self, season: u16) -> Result<Vec<Matchup>, FetchError> {
+        let req = self.league_request(season).view("mMatchup");
+        Ok(self.fetch::<MatchupResponse>(&req).await?.schedule)
     }
 
     /// Get data about matchups for a given scoringPeriod and matchupPeriod. Includes rosters.
     ///
-    /// To see what scoringPeriod and matchupPeriods are related, try at schedule_settings.matchup_periods from get_league_settings.
+    /// To see what scoringPeriod and matchupPeriods are related, use `get_season_calendar`. A bare
+    /// scoring period is looked up in the league's schedule; see `WeekRef`.
     pub async fn get_matchups_for_week(
         &self,
         season: u16,
-        matchup_period_id: u8,
-        scoring_period_id: u8,
+        week: impl Into<WeekRef>,
     ) -> Vec<Matchup> {
-        let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[
-                ("view", "mMatchup"),      //adds the `schedule` field
-                ("view", "mMatchupScore"), //adds rosters to the teams for the current scoring period
-                ("scoringPeriodId", scoring_period_id.to_string().as_str()), //required for rosters
-            ]);
-        let res = req.send().await.expect("LeagueSettingsResponse");
-        let data = res
-            .json::<MatchupResponse>()
+        self.try_get_matchups_for_week(season, week)
             .await
-            .expect("MatchupResponse Deserialization");
+            .expect("MatchupResponse Deserialization")
+    }
+
+    /// Like `get_matchups_for_week`, but returns failures instead of panicking.
+    pub async fn try_get_matchups_for_week(
+        &self,
+        season: u16,
+        week: impl Into<WeekRef>,
+    ) -> Result<Vec<Matchup>, FetchError> {
+        let Week {
+            matchup_period_id,
+            scoring_period_id,
+        } = self.try_resolve_week(season, week.into()).await?;
+        let req = self
+            .league_request(season)
+            .view("mMatchup") //adds the `schedule` field
+            .view("mMatchupScore") //adds rosters to the teams for the current scoring period
+            .scoring_period(scoring_period_id); //required for rosters
+        let data = self.fetch::<MatchupResponse>(&req).await?;
         let matchups = data
             .schedule
             .iter()
             .filter(|x| x.matchup_period_id == matchup_period_id)
             .map(|x| x.to_owned())
             .collect::<Vec<_>>();
-        matchups
+        Ok(matchups)
+    }
+
+    /// Get matchups for a week with live scoring, including rosters and in-progress points.
+    pub async fn get_live_matchups(&self, season: u16, week: impl Into<WeekRef>) -> Vec<Matchup> {
+        self.try_get_live_matchups(season, week)
+            .await
+            .expect("MatchupResponse Deserialization")
+    }
+
+    /// Like `get_live_matchups`, but returns failures instead of panicking.
+    pub async fn try_get_live_matchups(
+        &self,
+        season: u16,
+        week: impl Into<WeekRef>,
+    ) -> Result<Vec<Matchup>, FetchError> {
+        let Week {
+            matchup_period_id,
+            scoring_period_id,
+        } = self.try_resolve_week(season, week.into()).await?;
+        let req = self
+            .league_request(season)
+            .view("mMatchupScore")
+            .view("mLiveScoring")
+            .scoring_period(scoring_period_id);
+        let data = self.fetch::<MatchupResponse>(&req).await?;
+        Ok(data
+            .schedule
+            .into_iter()
+            .filter(|m| m.matchup_period_id == matchup_period_id)
+            .collect())
+    }
+
+    /// Get player-by-player scoring for every matchup in a week, starters and bench.
+    pub async fn get_box_scores(&self, season: u16, week: impl Into<WeekRef>) -> Vec<BoxScore> {
+        let Week {
+            matchup_period_id,
+            scoring_period_id,
+        } = self.resolve_week(season, week.into()).await;
+        let req = self
+            .league_request(season)
+            .view("mBoxscore")
+            .view("mMatchupScore")
+            .view("mScoreboard")
+            .scoring_period(scoring_period_id);
+        let data = self
+            .fetch::<MatchupResponse>(&req)
+            .await
+            .expect("MatchupResponse Deserialization");
+        data.schedule
+            .iter()
+            .filter(|m| m.matchup_period_id == matchup_period_id)
+            .filter_map(|m| BoxScore::from_matchup(m, season, scoring_period_id))
+            .collect()
+    }
+
+    /// Get how the season's scoring periods, matchup periods and dates line up.
+    pub async fn get_season_calendar(&self, season: u16) -> SeasonCalendar {
+        let status = self.get_league_status(season).await;
+        let settings = self.get_league_settings(season).await;
+        let pro_teams = self.get_pro_team_schedules(season).await;
+        SeasonCalendar::new(season, &status, &settings.schedule_settings).with_dates(&pro_teams)
+    }
+
+    /// Get the settings, teams, members and full schedule for a season.
+    pub async fn get_season_snapshot(&self, season: u16) -> Result<SeasonSnapshot, String> {
+        Ok(SeasonSnapshot {
+            season,
+            settings: self.try_get_league_settings(season).await?,
+            teams: self.try_get_team_data(season).await?,
+            members: self.get_league_members(season).await?,
+            matchups: self.try_get_matchups(season).await?,
+        })
+    }
+
+    /// Walk every season of the league up to and including `season`.
+    ///
+    /// Seasons are discovered through `previous_seasons` on the league status.
+    pub async fn get_league_history(&self, season: u16) -> Result<LeagueHistory, String> {
+        let status = self.try_get_league_status(season).await?;
+        let mut seasons = status.previous_seasons.unwrap_or_default();
+        seasons.push(season);
+        seasons.sort();
+        seasons.dedup();
+        let mut snapshots = Vec::new();
+        for s in seasons {
+            snapshots.push(self.get_season_snapshot(s).await?);
+        }
+        Ok(LeagueHistory::new(snapshots))
+    }
+
+    /// Estimate how a trade changes both teams' projected starting lineup points and playoff odds
+    /// for the rest of the season.
+    pub async fn analyze_trade(&self, season: u16, trade: &Trade) -> Result<TradeAnalysis, String> {
+        let status = self.try_get_league_status(season).await?;
+        let settings = self.try_get_league_settings(season).await?;
+        let teams = self
+            .try_get_teams_at_week(season, status.latest_scoring_period as u8)
+            .await?;
+        let schedule = self.try_get_matchups(season).await?;
+        let pro_teams = self.try_get_pro_team_schedules(season).await?;
+        let mut analyzer = TradeAnalyzer::new(
+            &settings,
+            season,
+            &teams,
+            &schedule,
+            status.current_matchup_period as u8,
+        );
+        analyzer.bye_weeks = pro_teams
+            .iter()
+            .map(|t| (t.id.clone(), t.bye_week))
+            .collect();
+        analyzer.analyze(trade)
+    }
+
+    /// Compare ESPN's projections to actual points for every rostered player across the scoring periods.
+    pub async fn get_projection_accuracy(
+        &self,
+        season: u16,
+        weeks: impl IntoIterator<Item = impl Into<WeekRef>>,
+    ) -> AccuracyReport {
+        let mut samples = Vec::new();
+        for week in weeks {
+            let scoring_period_id = week.into().scoring_period_id();
+            let teams = self.get_teams_at_week(season, scoring_period_id).await;
+            samples.append(&mut samples_from_teams(&teams, season, scoring_period_id));
+        }
+        AccuracyReport::from_samples(samples)
     }
 
     // pub async fn get_historical_scoreboard_for_week(
@@ -262,9 +475,39 @@ impl EspnClient {
     pub async fn get_free_agents_for_week(
         &self,
         season: i16,
-        scoring_period_id: u8,
+        week: impl Into<WeekRef>,
         limit: u8,
     ) -> Vec<FreeAgent> {
+        self.get_free_agent_response(season, week, limit)
+            .await
+            .players
+    }
+
+    /// Get free agents along with how each position fares against each NFL team.
+    pub async fn get_free_agent_response(
+        &self,
+        season: i16,
+        week: impl Into<WeekRef>,
+        limit: u8,
+    ) -> FreeAgentResponse {
+        self.try_get_free_agent_response(season, week, limit)
+            .await
+            .expect("JSON")
+    }
+
+    /// Like `get_free_agent_response`, but returns failures instead of panicking.
+    pub async fn try_get_free_agent_response(
+        &self,
+        season: i16,
+        week: impl Into<WeekRef>,
+        limit: u8,
+    ) -> Result<FreeAgentResponse, FetchError> {
+        let req = self.free_agent_request(season as u16, week.into().scoring_period_id(), limit);
+        self.fetch::<FreeAgentResponse>(&req).await
+    }
+
+    /// The request `get_free_agent_response` makes, for reading the response with `get_raw`.
+    pub fn free_agent_request(&self, season: u16, scoring_period_id: u8, limit: u8) -> EspnRequest {
         let free_agent_header_value = json!(
         {
             "players": {
@@ -277,21 +520,94 @@ impl EspnClient {
                 "sortPriority": 1
             }
         }});
-        let req = self
-            .client
-            .get(format!(
-                "{}/seasons/{}/segments/0/leagues/{}",
-                &self.base_url, season, &self.league_id
-            ))
-            .query(&[
-                ("scoringPeriodId", scoring_period_id.to_string().as_str()),
-                ("view", "kona_player_info"),
-            ])
-            .header("x-fantasy-filter", free_agent_header_value.to_string());
-        dbg!(&req);
-        let res = req.send().await.expect("Free Agents");
-        let data = res.json::<FreeAgentResponse>().await.expect("JSON");
-        data.players
+        self.league_request(season)
+            .scoring_period(scoring_period_id)
+            .view("kona_player_info")
+            .filter(free_agent_header_value)
+    }
+
+    /// Get the cards of the given players, rostered or not, with their stat lines for the season.
+    pub async fn get_player_cards(&self, season: u16, players: &[PlayerId]) -> Vec<PlayerCard> {
+        let req = self.player_card_request(season, players);
+        self.fetch::<PlayerCardResponse>(&req)
+            .await
+            .expect("PlayerCardResponse Deserialization")
+            .players
+    }
+
+    /// The request `get_player_cards` makes, for reading the response with `get_raw`.
+    pub fn player_card_request(&self, season: u16, players: &[PlayerId]) -> EspnRequest {
+        let ids = players.iter().map(|p| p.0).collect::<Vec<_>>();
+        let player_card_header_value = json!(
+        {
+            "players": {
+            "filterIds": {
+                "value": ids
+            },
+            "filterStatsForTopScoringPeriodIds": {
+                "value": 17,
+                "additionalValue": [format!("00{}", season), format!("10{}", season)]
+            }
+        }});
+        self.league_request(season)
+            .view("kona_playercard")
+            .filter(player_card_header_value)
+    }
+
+    /// Get every NFL team and its schedule for the season.
+    pub async fn get_pro_team_schedules(&self, season: u16) -> Vec<ProTeam> {
+        self.try_get_pro_team_schedules(season)
+            .await
+            .expect("ProTeamScheduleResponse Deserialization")
+    }
+
+    /// Like `get_pro_team_schedules`, but returns failures instead of panicking.
+    pub async fn try_get_pro_team_schedules(
+        &self,
+        season: u16,
+    ) -> Result<Vec<ProTeam>, FetchError> {
+        let req = EspnRequest::season(season).view("proTeamSchedules_wl");
+        Ok(self
+            .fetch::<ProTeamScheduleResponse>(&req)
+            .await?
+            .settings
+            .pro_teams)
+    }
+
+    /// Ranked add/drop suggestions for a team, considering each player's matchup in the scoring period.
+    ///
+    /// # Arguments
+    ///
+    /// * limit - How many free agents to consider, by ownership.
+    pub async fn recommend_waivers(
+        &self,
+        season: u16,
+        team: TeamId,
+        week: impl Into<WeekRef>,
+        limit: u8,
+    ) -> Result<Vec<WaiverRecommendation>, String> {
+        let scoring_period_id = week.into().scoring_period_id();
+        let settings = self.try_get_league_settings(season).await?;
+        let teams = self.try_get_teams_at_week(season, scoring_period_id).await?;
+        let free_agents = self
+            .try_get_free_agent_response(season as i16, scoring_period_id, limit)
+            .await?;
+        let pro_teams = self.try_get_pro_team_schedules(season).await?;
+        let opponents = opponents_for_week(&pro_teams, scoring_period_id);
+        let team = teams
+            .iter()
+            .find(|t| t.id == team)
+            .ok_or(format!("No team {} for season {}", team, season))?;
+        Ok(WaiverRecommender::new(
+            &settings.acquisition_settings,
+            settings.roster_settings.roster_size(),
+            &free_agents.players,
+            &free_agents.position_against_opponent,
+            &opponents,
+            season,
+            scoring_period_id,
+        )
+        .recommend(team))
     }
 
     /// Cached implementation to get overall team data for a season in the league.
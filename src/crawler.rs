@@ -0,0 +1,374 @@
+//! Walk a range of league ids, sorting out which leagues are public and collecting from those
+//! that are.
+//!
+//! ```no_run
+//! # use espn_fantasy_football::{client::EspnClient, crawler::{Crawler, LeagueSink}};
+//! struct Names;
+//!
+//! #[async_trait::async_trait(?Send)]
+//! impl LeagueSink for Names {
+//!     async fn collect(&self, client: &EspnClient, season: u16) -> Result<(), String> {
+//!         let teams = client.try_get_team_data(season).await.map_err(|e| e.to_string())?;
+//!         for team in teams {
+//!             println!("{}: {}", client.league_id, team.name);
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! let mut crawler = Crawler::public(2023, 5.0)?;
+//! crawler.checkpoint = Some("crawl.json".into());
+//! let progress = crawler.crawl(1..100_000, &Names).await?;
+//! println!("{} public leagues", progress.tally.public);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    ops::Range,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::{stream, FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    client::EspnClient,
+    source::{DataSource, EspnRequest, FetchError, HttpSource, RateLimitedSource},
+};
+
+/// What crawling one league id found.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum LeagueOutcome {
+    Public,
+    /// The league exists but needs cookies to read.
+    Private,
+    NotFound,
+    /// The request failed some other way, or the sink couldn't collect from a public league.
+    Error(String),
+}
+
+/// What to gather from each public league.
+///
+/// Sinks are called for several leagues at once, so one that keeps what it collects needs
+/// interior mutability, such as a `RefCell`. Use the client's `try_` getters, which return
+/// failures rather than panicking; a sink that panics anyway only errors its own league.
+#[async_trait(?Send)]
+pub trait LeagueSink {
+    async fn collect(&self, client: &EspnClient, season: u16) -> Result<(), String>;
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Tally {
+    pub public: usize,
+    pub private: usize,
+    pub not_found: usize,
+    pub errors: usize,
+}
+
+/// Progress through a crawl, saved after every league so a stopped crawl picks up where it
+/// left off.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Checkpoint {
+    pub season: u16,
+    /// The league ids being crawled. A checkpoint only resumes a crawl of the same range.
+    pub leagues: Range<i32>,
+    /// Every league id in the range below this has been crawled.
+    pub next: i32,
+    /// League ids at or above `next` that finished early, since leagues are crawled at once.
+    pub finished: BTreeSet<i32>,
+    pub tally: Tally,
+    pub public: Vec<i32>,
+    /// Leagues that errored and why. Transient failures are retried before landing here, but
+    /// nothing here is retried on resume; crawl them again with a new checkpoint.
+    pub errors: BTreeMap<i32, String>,
+}
+
+impl Checkpoint {
+    pub fn new(season: u16, leagues: Range<i32>) -> Checkpoint {
+        Checkpoint {
+            season,
+            next: leagues.start,
+            leagues,
+            ..Default::default()
+        }
+    }
+
+    /// The checkpoint saved at `path`, or `None` if nothing has been saved there yet.
+    pub fn load(path: &Path) -> io::Result<Option<Checkpoint>> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves through a temporary file, so a crash mid-write leaves the previous checkpoint intact.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string(self)?;
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, json)?;
+        std::fs::rename(&partial, path)
+    }
+
+    pub fn is_crawled(&self, league_id: i32) -> bool {
+        league_id < self.next || self.finished.contains(&league_id)
+    }
+
+    pub fn record(&mut self, league_id: i32, outcome: LeagueOutcome) {
+        match outcome {
+            LeagueOutcome::Public => {
+                self.tally.public += 1;
+                self.public.push(league_id);
+            }
+            LeagueOutcome::Private => self.tally.private += 1,
+            LeagueOutcome::NotFound => self.tally.not_found += 1,
+            LeagueOutcome::Error(e) => {
+                self.tally.errors += 1;
+                self.errors.insert(league_id, e);
+            }
+        }
+        self.finished.insert(league_id);
+        while self.finished.remove(&self.next) {
+            self.next += 1;
+        }
+    }
+}
+
+/// Crawls league ids for one season, sharing a single data source across every league.
+pub struct Crawler {
+    source: Arc<dyn DataSource>,
+    pub season: u16,
+    /// How many leagues are crawled at once.
+    pub concurrency: usize,
+    /// Where progress is saved. `None` keeps it in memory only.
+    pub checkpoint: Option<PathBuf>,
+    /// How many more times to probe a league after a transient failure, such as a 429 or 503.
+    pub retries: u32,
+    /// How long to wait before the first retry, doubling for each one after.
+    pub backoff: Duration,
+}
+
+impl Crawler {
+    pub fn new(source: Arc<dyn DataSource>, season: u16) -> Crawler {
+        Crawler {
+            source,
+            season,
+            concurrency: 8,
+            checkpoint: None,
+            retries: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// Crawls ESPN without cookies, so only public leagues are readable, sending at most
+    /// `requests_per_second`, which must be positive.
+    pub fn public(season: u16, requests_per_second: f64) -> io::Result<Crawler> {
+        let interval = Duration::try_from_secs_f64(1.0 / requests_per_second)
+            .ok()
+            .filter(|_| requests_per_second > 0.0)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Can't send {} requests per second", requests_per_second),
+                )
+            })?;
+        let source = RateLimitedSource::new(HttpSource::new("", ""), interval);
+        Ok(Crawler::new(Arc::new(source), season))
+    }
+
+    /// Crawls every league id in `leagues` not already in the checkpoint, passing each public
+    /// league to `sink`. A saved checkpoint must be for the same season and range.
+    pub async fn crawl(
+        &self,
+        leagues: Range<i32>,
+        sink: &dyn LeagueSink,
+    ) -> io::Result<Checkpoint> {
+        let saved = match &self.checkpoint {
+            Some(path) => Checkpoint::load(path)?,
+            None => None,
+        };
+        let mut checkpoint = saved.unwrap_or_else(|| Checkpoint::new(self.season, leagues.clone()));
+        if checkpoint.season != self.season {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Checkpoint is for {}, not {}",
+                    checkpoint.season, self.season
+                ),
+            ));
+        }
+        if checkpoint.leagues != leagues {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Checkpoint is for leagues {:?}, not {:?}",
+                    checkpoint.leagues, leagues
+                ),
+            ));
+        }
+        let crawled = checkpoint.clone();
+        let mut outcomes = stream::iter(leagues.filter(move |id| !crawled.is_crawled(*id)))
+            .map(|league_id| async move { (league_id, self.crawl_league(league_id, sink).await) })
+            .buffer_unordered(self.concurrency.max(1));
+        while let Some((league_id, outcome)) = outcomes.next().await {
+            checkpoint.record(league_id, outcome);
+            if let Some(path) = &self.checkpoint {
+                checkpoint.save(path)?;
+            }
+        }
+        Ok(checkpoint)
+    }
+
+    async fn crawl_league(&self, league_id: i32, sink: &dyn LeagueSink) -> LeagueOutcome {
+        match self.probe(league_id).await {
+            Ok(()) => {
+                let client = EspnClient::with_source(league_id, self.source.clone());
+                let collected = AssertUnwindSafe(sink.collect(&client, self.season))
+                    .catch_unwind()
+                    .await;
+                match collected {
+                    Ok(Ok(())) => LeagueOutcome::Public,
+                    Ok(Err(e)) => LeagueOutcome::Error(e),
+                    Err(panic) => LeagueOutcome::Error(panic_message(panic)),
+                }
+            }
+            Err(FetchError::Unauthorized) => LeagueOutcome::Private,
+            Err(FetchError::NotFound) => LeagueOutcome::NotFound,
+            Err(e) => LeagueOutcome::Error(e.to_string()),
+        }
+    }
+
+    /// Whether the league can be read, retrying transient failures with backoff.
+    async fn probe(&self, league_id: i32) -> Result<(), FetchError> {
+        let probe = EspnRequest::league(self.season, league_id).view("mSettings");
+        let mut backoff = self.backoff;
+        let mut retries = self.retries;
+        loop {
+            match self.source.fetch(&probe).await {
+                Ok(_) => return Ok(()),
+                Err(e) if e.is_transient() && retries > 0 => {
+                    retries -= 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    format!("Sink panicked: {}", message)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::RefCell,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+
+    /// Leagues divisible by 3 are private, by 5 missing, and league 7 is down. League 11 is rate
+    /// limited the first time it's asked for.
+    #[derive(Default)]
+    struct Leagues {
+        limited: AtomicBool,
+    }
+
+    #[async_trait]
+    impl DataSource for Leagues {
+        async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+            let league_id: i32 = request.path.rsplit('/').next().unwrap().parse().unwrap();
+            match league_id {
+                7 => Err(FetchError::Status(503)),
+                11 if !self.limited.swap(true, Ordering::SeqCst) => Err(FetchError::Status(429)),
+                id if id % 3 == 0 => Err(FetchError::Unauthorized),
+                id if id % 5 == 0 => Err(FetchError::NotFound),
+                _ => Ok("{}".to_string()),
+            }
+        }
+    }
+
+    /// Panics on league 13.
+    #[derive(Default)]
+    struct Collected(RefCell<Vec<i32>>);
+
+    #[async_trait(?Send)]
+    impl LeagueSink for Collected {
+        async fn collect(&self, client: &EspnClient, _season: u16) -> Result<(), String> {
+            assert_ne!(client.league_id, 13, "Couldn't read league 13");
+            self.0.borrow_mut().push(client.league_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn classifies_leagues_and_resumes() {
+        let path = std::env::temp_dir().join(format!("espn-crawl-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut crawler = Crawler::new(Arc::new(Leagues::default()), 2023);
+        crawler.concurrency = 4;
+        crawler.checkpoint = Some(path.clone());
+        crawler.backoff = Duration::ZERO;
+
+        let sink = Collected::default();
+        let progress = crawler.crawl(1..16, &sink).await.unwrap();
+        assert_eq!(
+            progress.tally,
+            Tally {
+                public: 6,
+                private: 5,
+                not_found: 2,
+                errors: 2
+            }
+        );
+        assert_eq!(progress.next, 16);
+        assert!(progress.finished.is_empty());
+        assert_eq!(progress.errors[&7], "Unexpected status 503");
+        assert!(progress.errors[&13].contains("Couldn't read league 13"));
+        let mut collected = sink.0.take();
+        collected.sort();
+        assert_eq!(collected, vec![1, 2, 4, 8, 11, 14]);
+        assert_eq!(Checkpoint::load(&path).unwrap(), Some(progress.clone()));
+
+        let sink = Collected::default();
+        let resumed = crawler.crawl(1..16, &sink).await.unwrap();
+        assert!(sink.0.take().is_empty());
+        assert_eq!(resumed, progress);
+
+        // A checkpoint only resumes the same season and range.
+        for leagues in [0..16, 1..20, 20..30] {
+            let error = crawler.crawl(leagues, &sink).await.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+        crawler.season = 2022;
+        assert!(crawler.crawl(1..16, &sink).await.is_err());
+        assert!(sink.0.take().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn needs_a_positive_request_rate() {
+        for rate in [0.0, -1.0, f64::NAN] {
+            let error = Crawler::public(2023, rate).err().unwrap();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Record the responses `EspnClient` receives, then serve them back in tests.
+//!
+//! ```no_run
+//! # use espn_fantasy_football::client::EspnClient;
+//! # async fn run() {
+//! // Once, against ESPN:
+//! let client = EspnClient::recording(1234, "", "", "tests/fixtures");
+//! client.get_team_data(2023).await;
+//!
+//! // In tests, with no network:
+//! let client = EspnClient::replay(1234, "tests/fixtures");
+//! client.get_team_data(2023).await;
+//! # }
+//! ```
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::source::{DataSource, EspnRequest, FetchError, HttpSource};
+
+/// One saved request and what came back for it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Recording {
+    pub request: EspnRequest,
+    pub response: Result<String, FetchError>,
+}
+
+/// Where the recording of a request is kept in `dir`.
+///
+/// Every part of the request, including the league and the `x-fantasy-filter` header, goes into
+/// the file name, so two requests only share a recording when ESPN would answer them the same.
+pub fn recording_path(dir: &std::path::Path, request: &EspnRequest) -> PathBuf {
+    let season = request
+        .season_id()
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+    let mut views = request.views().join("+");
+    if let Some(scoring_period_id) = request.scoring_period_id() {
+        views = format!("{}_{}", views, scoring_period_id);
+    }
+    dir.join(season)
+        .join(format!("{}-{:016x}.json", views, request_hash(request)))
+}
+
+/// FNV-1a over the request, which unlike `DefaultHasher` is stable between Rust releases.
+fn request_hash(request: &EspnRequest) -> u64 {
+    let key = format!(
+        "{}|{}",
+        request,
+        request.filter.as_deref().unwrap_or_default()
+    );
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Passes requests through to another source and saves each response, errors included.
+pub struct RecordingSource<S> {
+    pub inner: S,
+    pub dir: PathBuf,
+}
+
+impl<S: DataSource> RecordingSource<S> {
+    pub fn new(inner: S, dir: impl Into<PathBuf>) -> RecordingSource<S> {
+        RecordingSource {
+            inner,
+            dir: dir.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: DataSource> DataSource for RecordingSource<S> {
+    async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+        let response = self.inner.fetch(request).await;
+        let recording = Recording {
+            request: request.clone(),
+            response: response.clone(),
+        };
+        let path = recording_path(&self.dir, request);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| FetchError::Transport(e.to_string()))?;
+        }
+        let json = serde_json::to_string_pretty(&recording)
+            .map_err(|e| FetchError::Transport(e.to_string()))?;
+        tokio::fs::write(&path, json)
+            .await
+            .map_err(|e| FetchError::Transport(e.to_string()))?;
+        response
+    }
+
+    fn http(&self) -> Option<&HttpSource> {
+        self.inner.http()
+    }
+}
+
+/// Serves responses saved by a `RecordingSource`.
+///
+/// Panics on a request that was never recorded, naming the request and the file it looked for,
+/// so a test can't quietly pass on missing data.
+pub struct ReplaySource {
+    pub dir: PathBuf,
+}
+
+impl ReplaySource {
+    pub fn new(dir: impl Into<PathBuf>) -> ReplaySource {
+        ReplaySource { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl DataSource for ReplaySource {
+    async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+        let path = recording_path(&self.dir, request);
+        let json = match tokio::fs::read_to_string(&path).await {
+            Ok(json) => json,
+            Err(e) => panic!(
+                "No recording for {} (filter: {:?}) at {}: {}",
+                request,
+                request.filter,
+                path.display(),
+                e
+            ),
+        };
+        let recording: Recording = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("Bad recording at {}: {}", path.display(), e));
+        recording.response
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Echo;
+
+    #[async_trait]
+    impl DataSource for Echo {
+        async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+            match request.scoring_period_id() {
+                Some(99) => Err(FetchError::NotFound),
+                _ => Ok(format!("{{\"path\":\"{}\"}}", request.path)),
+            }
+        }
+    }
+
+    /// An empty directory for one test's recordings.
+    fn recordings_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("espn-fixture-{}-{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn replays_what_was_recorded() {
+        let dir = recordings_dir("replays_what_was_recorded");
+        let recorder = RecordingSource::new(Echo, &dir);
+        let found = EspnRequest::league(2023, 1).view("mTeam");
+        let filtered = found.clone().filter(serde_json::json!({"players": {}}));
+        let missing = EspnRequest::league(2023, 1).scoring_period(99);
+        let expected = recorder.fetch(&found).await;
+        assert_eq!(recorder.fetch(&missing).await, Err(FetchError::NotFound));
+
+        let replay = ReplaySource::new(&dir);
+        assert_eq!(replay.fetch(&found).await, expected);
+        assert_eq!(replay.fetch(&missing).await, Err(FetchError::NotFound));
+        assert_ne!(
+            recording_path(&dir, &found),
+            recording_path(&dir, &filtered)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "No recording for")]
+    async fn replay_panics_on_unrecorded_requests() {
+        let replay = ReplaySource::new(recordings_dir("replay_panics_on_unrecorded_requests"));
+        let _ = replay
+            .fetch(&EspnRequest::league(2023, 1).view("mTeam"))
+            .await;
+    }
+}
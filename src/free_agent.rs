@@ -2,8 +2,11 @@ use std::collections::HashMap;
 
 use crate::team::TeamId;
 
-use super::id_maps::{PositionId, ProTeamId};
+use super::id_maps::ProTeamId;
 use super::player::{Player, PlayerId};
+use super::position::Position;
+use super::raw::Raw;
+use super::status::RosterStatus;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -12,6 +15,14 @@ pub struct FreeAgentResponse {
     #[serde(rename = "positionAgainstOpponent")]
     pub position_against_opponent: PositionalRatings,
 }
+
+impl Raw<FreeAgentResponse> {
+    /// A player along with the JSON ESPN sent for them.
+    pub fn player(&self, id: PlayerId) -> Option<Raw<FreeAgent>> {
+        let index = self.players.iter().position(|p| p.id == id)?;
+        self.at(&format!("/players/{}", index))
+    }
+}
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct FreeAgent {
     #[serde(rename = "draftAuctionValue")]
@@ -27,14 +38,14 @@ pub struct FreeAgent {
     pub on_team_id: TeamId,
     pub player: Player,
     pub ratings: HashMap<u8, FreeAgentRatings>,
-    pub status: String,
+    pub status: RosterStatus,
     #[serde(rename = "tradeLocked")]
     pub trade_locked: bool,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PositionalRatings {
     #[serde(rename = "positionalRatings")]
-    pub positional_ratings: HashMap<PositionId, Ratings>,
+    pub positional_ratings: HashMap<Position, Ratings>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Ratings {
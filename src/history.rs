@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    league::{LeagueMember, LeagueSettings},
+    matchup::{Matchup, MatchupKind, TeamMatchupPerformance},
+    members::MemberId,
+    team::{Team, TeamId},
+};
+
+/// Everything needed to analyze a single season of the league after the fact.
+#[derive(Debug, Serialize)]
+pub struct SeasonSnapshot {
+    pub season: u16,
+    pub settings: LeagueSettings,
+    pub teams: Vec<Team>,
+    pub members: Vec<LeagueMember>,
+    pub matchups: Vec<Matchup>,
+}
+
+impl SeasonSnapshot {
+    /// The member who owned the team this season.
+    ///
+    /// Co-owned teams are credited to the primary owner.
+    pub fn owner_of(&self, team: &TeamId) -> Option<&MemberId> {
+        let team = self.teams.iter().find(|t| &t.id == team)?;
+        match &team.primary_owner {
+            Some(owner) => Some(owner),
+            None => team.owners.as_ref()?.first(),
+        }
+    }
+
+    /// The team a member owned this season, if any.
+    pub fn team_of(&self, member: &MemberId) -> Option<&Team> {
+        self.teams
+            .iter()
+            .find(|t| self.owner_of(&t.id) == Some(member))
+    }
+
+    /// Whether the matchup was in the winners bracket. Consolation games don't count.
+    pub fn is_playoff(&self, matchup: &Matchup) -> bool {
+        matchup.kind() == MatchupKind::Playoff
+    }
+
+    /// Every completed, non-bye game this season, resolved to the members who played it.
+    pub fn games(&self) -> Vec<GameResult> {
+        self.matchups
+            .iter()
+            .filter(|m| m.winner.is_decided())
+            .filter_map(|m| {
+                let home = self.member_score(m.home.as_ref()?)?;
+                let away = self.member_score(m.away.as_ref()?)?;
+                Some(GameResult {
+                    season: self.season,
+                    matchup_period_id: m.matchup_period_id,
+                    kind: m.kind(),
+                    home,
+                    away,
+                })
+            })
+            .collect()
+    }
+
+    fn member_score(&self, performance: &TeamMatchupPerformance) -> Option<MemberScore> {
+        Some(MemberScore {
+            member: self.owner_of(&performance.team_id)?.clone(),
+            team: performance.team_id,
+            points: performance.total_points,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MemberScore {
+    pub member: MemberId,
+    pub team: TeamId,
+    pub points: f32,
+}
+
+/// A completed game between two members.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct GameResult {
+    pub season: u16,
+    pub matchup_period_id: u8,
+    pub kind: MatchupKind,
+    pub home: MemberScore,
+    pub away: MemberScore,
+}
+
+impl GameResult {
+    /// Whether the game was in the winners bracket.
+    pub fn is_playoff(&self) -> bool {
+        self.kind == MatchupKind::Playoff
+    }
+    /// Absolute difference between the two scores.
+    pub fn margin(&self) -> f32 {
+        (self.home.points - self.away.points).abs()
+    }
+    /// The winning side, or `None` for a tie.
+    pub fn winner(&self) -> Option<&MemberScore> {
+        if self.home.points > self.away.points {
+            Some(&self.home)
+        } else if self.away.points > self.home.points {
+            Some(&self.away)
+        } else {
+            None
+        }
+    }
+    /// The losing side, or `None` for a tie.
+    pub fn loser(&self) -> Option<&MemberScore> {
+        if self.home.points > self.away.points {
+            Some(&self.away)
+        } else if self.away.points > self.home.points {
+            Some(&self.home)
+        } else {
+            None
+        }
+    }
+    pub fn involves(&self, member: &MemberId) -> bool {
+        &self.home.member == member || &self.away.member == member
+    }
+    /// The score of `member` in this game, followed by their opponent's.
+    pub fn sides_for(&self, member: &MemberId) -> Option<(&MemberScore, &MemberScore)> {
+        if &self.home.member == member {
+            Some((&self.home, &self.away))
+        } else if &self.away.member == member {
+            Some((&self.away, &self.home))
+        } else {
+            None
+        }
+    }
+}
+
+/// The all-time record of one member against another.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct HeadToHead {
+    pub member: MemberId,
+    pub opponent: MemberId,
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+    pub points_for: f32,
+    pub points_against: f32,
+    pub playoff_meetings: Vec<GameResult>,
+    pub biggest_blowout: Option<GameResult>,
+    pub closest_game: Option<GameResult>,
+}
+
+impl HeadToHead {
+    pub fn games_played(&self) -> u32 {
+        self.wins + self.losses + self.ties
+    }
+}
+
+/// Every season of a league, for analysis across years.
+///
+/// Results are keyed on [MemberId] rather than [TeamId], since team ids are reassigned
+/// between seasons and members may take over a different team.
+#[derive(Debug, Serialize)]
+pub struct LeagueHistory {
+    pub seasons: Vec<SeasonSnapshot>,
+}
+
+impl LeagueHistory {
+    pub fn new(mut seasons: Vec<SeasonSnapshot>) -> LeagueHistory {
+        seasons.sort_by_key(|s| s.season);
+        LeagueHistory { seasons }
+    }
+
+    /// All completed games across every season, oldest first.
+    pub fn games(&self) -> Vec<GameResult> {
+        self.seasons.iter().flat_map(|s| s.games()).collect()
+    }
+
+    /// Every member who owned a team in any season.
+    pub fn members(&self) -> HashSet<MemberId> {
+        self.seasons
+            .iter()
+            .flat_map(|s| s.teams.iter().filter_map(|t| s.owner_of(&t.id).cloned()))
+            .collect()
+    }
+
+    /// The most recent details known for a member.
+    pub fn member_details(&self, member: &MemberId) -> Option<&LeagueMember> {
+        self.seasons
+            .iter()
+            .rev()
+            .find_map(|s| s.members.iter().find(|m| &m.id == member))
+    }
+
+    /// The record of `member` against `opponent` across all seasons.
+    pub fn head_to_head(&self, member: &MemberId, opponent: &MemberId) -> HeadToHead {
+        let games = self
+            .games()
+            .into_iter()
+            .filter(|g| g.involves(member) && g.involves(opponent))
+            .collect::<Vec<_>>();
+        head_to_head_from_games(member, opponent, &games)
+    }
+
+    /// Head to head records for every pair of members who have played each other, from the
+    /// perspective of each member.
+    pub fn rivalries(&self) -> Vec<HeadToHead> {
+        let mut pairings: HashMap<(MemberId, MemberId), Vec<GameResult>> = HashMap::new();
+        for game in self.games() {
+            let home = game.home.member.clone();
+            let away = game.away.member.clone();
+            pairings
+                .entry((home.clone(), away.clone()))
+                .or_default()
+                .push(game.clone());
+            pairings.entry((away, home)).or_default().push(game);
+        }
+        let mut rivalries = pairings
+            .iter()
+            .map(|((member, opponent), games)| head_to_head_from_games(member, opponent, games))
+            .collect::<Vec<_>>();
+        rivalries.sort_by(|a, b| {
+            b.games_played()
+                .cmp(&a.games_played())
+                .then_with(|| a.member.0.cmp(&b.member.0))
+                .then_with(|| a.opponent.0.cmp(&b.opponent.0))
+        });
+        rivalries
+    }
+}
+
+fn head_to_head_from_games(
+    member: &MemberId,
+    opponent: &MemberId,
+    games: &[GameResult],
+) -> HeadToHead {
+    let mut record = HeadToHead {
+        member: member.clone(),
+        opponent: opponent.clone(),
+        wins: 0,
+        losses: 0,
+        ties: 0,
+        points_for: 0.0,
+        points_against: 0.0,
+        playoff_meetings: Vec::new(),
+        biggest_blowout: None,
+        closest_game: None,
+    };
+    for game in games {
+        let (ours, theirs) = match game.sides_for(member) {
+            Some(sides) => sides,
+            None => continue,
+        };
+        record.points_for += ours.points;
+        record.points_against += theirs.points;
+        match game.winner() {
+            Some(w) if &w.member == member => record.wins += 1,
+            Some(_) => record.losses += 1,
+            None => record.ties += 1,
+        }
+        if game.is_playoff() {
+            record.playoff_meetings.push(game.clone());
+        }
+        if record
+            .biggest_blowout
+            .as_ref()
+            .is_none_or(|b| game.margin() > b.margin())
+        {
+            record.biggest_blowout = Some(game.clone());
+        }
+        if record
+            .closest_game
+            .as_ref()
+            .is_none_or(|c| game.margin() < c.margin())
+        {
+            record.closest_game = Some(game.clone());
+        }
+    }
+    record
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{matchup::PlayoffTierType, synthetic::LeagueGenerator};
+
+    fn snapshot(seed: u64, season: u16) -> SeasonSnapshot {
+        let mut generator = LeagueGenerator::new(seed);
+        generator.season = season;
+        let league = generator.generate();
+        SeasonSnapshot {
+            season,
+            settings: league.settings,
+            teams: league.teams,
+            members: league.members,
+            matchups: league.schedule,
+        }
+    }
+
+    #[test]
+    fn head_to_head_counts_only_winners_bracket_as_playoffs() {
+        let mut season = snapshot(3, 2023);
+        let playoff = season
+            .matchups
+            .iter()
+            .find(|m| m.kind() == MatchupKind::Playoff)
+            .unwrap()
+            .clone();
+        let mut consolation = playoff.clone();
+        consolation.id = 999;
+        consolation.playoff_tier_type = Some(PlayoffTierType::WinnersConsolationLadder);
+        season.matchups.push(consolation.clone());
+        assert!(season.is_playoff(&playoff));
+        assert!(!season.is_playoff(&consolation));
+
+        let home = season
+            .owner_of(&playoff.home.as_ref().unwrap().team_id)
+            .unwrap()
+            .clone();
+        let away = season
+            .owner_of(&playoff.away.as_ref().unwrap().team_id)
+            .unwrap()
+            .clone();
+        let history = LeagueHistory::new(vec![season]);
+        let record = history.head_to_head(&home, &away);
+        let games = history
+            .games()
+            .into_iter()
+            .filter(|g| g.involves(&home) && g.involves(&away))
+            .count() as u32;
+        assert_eq!(record.games_played(), games);
+        assert_eq!(record.playoff_meetings.len(), 1);
+        assert_eq!(history.head_to_head(&away, &home).wins, record.losses);
+    }
+
+    #[test]
+    fn rivalries_cover_both_sides_of_every_pairing() {
+        let history = LeagueHistory::new(vec![snapshot(5, 2023), snapshot(5, 2022)]);
+        assert_eq!(history.seasons[0].season, 2022);
+        let games = history.games().len() as u32;
+        let rivalries = history.rivalries();
+        assert_eq!(
+            rivalries.iter().map(|r| r.games_played()).sum::<u32>(),
+            2 * games
+        );
+        for rivalry in &rivalries {
+            let reverse = history.head_to_head(&rivalry.opponent, &rivalry.member);
+            assert_eq!(reverse.wins, rivalry.losses);
+            assert_eq!(reverse.points_for, rivalry.points_against);
+        }
+        assert!(rivalries
+            .windows(2)
+            .all(|w| w[0].games_played() >= w[1].games_played()));
+    }
+}
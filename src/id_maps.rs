@@ -1,69 +1,40 @@
 use phf::phf_map;
 use serde::{Deserialize, Serialize};
-#[derive(Debug, Deserialize, Serialize, Default, Clone, Eq, PartialEq, Hash)]
-pub struct PositionId(pub u64);
-impl PositionId {
-    pub fn to_string(&self) -> &'static str {
-        match POSITION_ID_MAP.get(&self.0.to_string()) {
-            Some(f) => f,
-            None => "Unknown",
-        }
-    }
-}
-// Adapted from https://github.com/mkreiser/ESPN-Fantasy-Football-API/blob/main/src/constants.js;
-static POSITION_ID_MAP: phf::Map<&'static str, &'static str> = phf_map! {
-  "0" => "QB",
-  "1" => "TQB",
-  "2" => "RB",
-  "3" => "RB/WR",
-  "4" => "WR",
-  "5" => "WR/TE",
-  "6" => "TE",
-  "7" => "OP",
-  "8" => "DT",
-  "9" => "DE",
-  "10" => "LB",
-  "11" => "DL",
-  "12" => "CB",
-  "13" => "S",
-  "14" => "DB",
-  "15" => "DP",
-  "16" => "D/ST",
-  "17" => "K",
-  "18" => "P",
-  "19" => "HC",
-  "20" => "Bench",
-  "21" => "IR",
-  "22" => "Unknown", // TODO: Figure out what this is
-  "23" => "RB/WR/TE",
-  "24" => "Unknown" // TODO: Figure out what this is
-};
 
+use super::registry;
 #[derive(Debug, Deserialize, Serialize, Default, Clone, Eq, PartialEq, Hash)]
 pub struct ProTeamId(pub i8);
 impl ProTeamId {
     pub fn to_name(&self) -> &'static str {
-        &self.identifiers().name
+        self.identifiers().name
     }
     pub fn to_abbreviation(&self) -> &'static str {
-        &self.identifiers().abbreviation
+        self.identifiers().abbreviation
     }
-    pub fn identifiers(&self) -> &TeamIdentifiers {
-        match PRO_TEAM_ID_MAP.get(&self.0.to_string()) {
-            Some(f) => f,
-            None => &TeamIdentifiers {
-                name: "Unknown",
-                abbreviation: "UNK",
-            },
-        }
+    pub fn identifiers(&self) -> &'static TeamIdentifiers {
+        registry::pro_team(self)
+            .or_else(|| PRO_TEAM_ID_MAP.get(&self.0.to_string()))
+            .unwrap_or(&UNKNOWN_TEAM)
     }
 }
+
+static UNKNOWN_TEAM: TeamIdentifiers = TeamIdentifiers {
+    name: "Unknown",
+    abbreviation: "UNK",
+};
+
 #[derive(Debug, Deserialize, Serialize, Default, Clone, Eq, PartialEq)]
 pub struct TeamIdentifiers {
     name: &'static str,
     abbreviation: &'static str,
 }
 
+impl TeamIdentifiers {
+    pub fn new(name: &'static str, abbreviation: &'static str) -> TeamIdentifiers {
+        TeamIdentifiers { name, abbreviation }
+    }
+}
+
 static PRO_TEAM_ID_MAP: phf::Map<&'static str, TeamIdentifiers> = phf_map! {
   "-1" => TeamIdentifiers { name: "Bye", abbreviation: "Bye"},
   "1" => TeamIdentifiers { name: "Atlanta Falcons", abbreviation: "ATL"},
@@ -103,21 +74,48 @@ static PRO_TEAM_ID_MAP: phf::Map<&'static str, TeamIdentifiers> = phf_map! {
 #[derive(Debug, Deserialize, Serialize, Default, Clone, Eq, PartialEq, Hash)]
 pub struct StatId(pub u64);
 
+static UNKNOWN_STAT: StatIdentifiers = StatIdentifiers {
+    name: "Unknown",
+    field_name: "unknown",
+    category: StatCategory::Miscellaneous,
+    unit: StatUnit::Count,
+    derived: false,
+};
+
 impl StatId {
-    pub fn identifiers(&self) -> &StatIdentifiers {
-        match STAT_ID_MAP.get(&self.0.to_string()) {
-            Some(ids) => ids,
-            None => &StatIdentifiers {
-                name: "Unknown",
-                field_name: "unknown", //format!("unknown_{}", &self.0.to_string()).as_str(),
-            },
-        }
+    pub fn identifiers(&self) -> &'static StatIdentifiers {
+        registry::stat(self).unwrap_or(&UNKNOWN_STAT)
     }
     pub fn to_name(&self) -> &'static str {
-        &self.identifiers().name
+        self.identifiers().name
     }
     pub fn to_field_name(&self) -> &'static str {
-        &self.identifiers().field_name
+        self.identifiers().field_name
+    }
+    pub fn category(&self) -> StatCategory {
+        self.identifiers().category
+    }
+    pub fn unit(&self) -> StatUnit {
+        self.identifiers().unit
+    }
+    /// Whether ESPN calculates the stat from other stats, e.g. "Every 25 Passing Yards".
+    pub fn is_derived(&self) -> bool {
+        self.identifiers().derived
+    }
+    /// Whether the stat is in the catalog. Unknown stats are reported as "Unknown".
+    pub fn is_known(&self) -> bool {
+        registry::stat(self).is_some()
+    }
+    /// The reverse of `to_field_name`.
+    pub fn from_field_name(field_name: &str) -> Option<StatId> {
+        StatId::all()
+            .into_iter()
+            .find(|(_, ids)| ids.field_name == field_name)
+            .map(|(id, _)| id)
+    }
+    /// Every known football stat, including registered ones, ordered by id.
+    pub fn all() -> Vec<(StatId, &'static StatIdentifiers)> {
+        registry::stats()
     }
 }
 
@@ -125,198 +123,310 @@ impl StatId {
 pub struct StatIdentifiers {
     name: &'static str,
     field_name: &'static str,
+    category: StatCategory,
+    unit: StatUnit,
+    derived: bool,
 }
 
-// Adapted from https://github.com/mkreiser/ESPN-Fantasy-Football-API/blob/main/src/player-stats/player-stats.js
-// use the `find_unmapped_stats` example to locate stats to add from your league for a given week
-static STAT_ID_MAP: phf::Map<&'static str, StatIdentifiers> = phf_map! {
-    "0" => StatIdentifiers {name: "Pass Attempts", field_name: "pass_attempts"},
-    "1" => StatIdentifiers {name: "Completed Passes", field_name: "passing_completions"},
-    "2" => StatIdentifiers {name: "Incomplete Passes", field_name: "passing_incompletions"},
-    "3" => StatIdentifiers {name: "Passing Yards", field_name: "passing_yards"},
-    "4" => StatIdentifiers {name: "Passing Touchdowns", field_name:"passing_touchdowns"},
-    "5" => StatIdentifiers {name: "Every 5 Passing Yards", field_name: "passing_yards_each_5"},
-    "6" => StatIdentifiers {name: "Every 10 Passing Yards", field_name: "passing_yards_each_10"},
-    "7" => StatIdentifiers {name: "Every 20 Passing Yards", field_name: "passing_yards_each_20"},
-    "8" => StatIdentifiers {name: "Every 25 Passing Yards", field_name: "passing_yards_each_25"},
-    "9" => StatIdentifiers {name: "Every 50 Passing Yards", field_name: "passing_yards_each_50"},
-    "10" => StatIdentifiers {name: "Every 100 Passing Yards", field_name: "passing_yards_each_100"},
-    "11" => StatIdentifiers {name: "Every 5 Passing Completions", field_name: "passing_completions_each_5"},
-    "12" => StatIdentifiers {name: "Every 10 Passing Completions", field_name: "passing_completions_each_10"},
-    "13" => StatIdentifiers {name: "Every 5 Passing Incompletions", field_name: "passing_incompletions_each_5"},
-    "14" => StatIdentifiers {name: "Every 10 Passing Incompletions", field_name: "passing_incompletions_each_10"},
-    "15" => StatIdentifiers {name: "40+ Yard TD Pass Bonus", field_name: "passing_touchdown_40_plus_bonus"},
-    "16" => StatIdentifiers {name: "50+ Yard TD Pass Bonus", field_name: "passing_touchdown_50_plus_bonus"},
-    "17" => StatIdentifiers {name: "300-399 yard passing game", field_name: "passing_300_to_399_yard_game"},
-    "18" => StatIdentifiers {name: "400+ yard passing game", field_name: "passing_over_400_yards"},
-    "19" => StatIdentifiers {name: "Passing 2 Point Conversions", field_name: "passing_two_point_conversions"},
-    "20" => StatIdentifiers {name: "Passing Interceptions", field_name: "passing_interceptions"},
-    
-    "23" => StatIdentifiers {name: "Rushing Attempts", field_name: "rushing_attempts"},
-    "24" => StatIdentifiers {name: "Rushing Yards", field_name: "rushing_yards"},
-    "25" => StatIdentifiers {name: "Rushing Touchdowns", field_name: "rushing_touchdowns"},
-    "26" => StatIdentifiers {name: "Rushing 2 Point Conversions", field_name:"rushing_two_point_conversions"},
-    "27" => StatIdentifiers {name: "Every 5 Rushing Yards", field_name: "rushing_yards_each_5"},
-    "28" => StatIdentifiers {name: "Every 10 Rushing Yards", field_name: "rushing_yards_each_10"},
-    "29" => StatIdentifiers {name: "Every 20 Rushing Yards", field_name: "rushing_yards_each_20"},
-    "30" => StatIdentifiers {name: "Every 25 Rushing Yards", field_name: "rushing_yards_each_25"},
-    "31" => StatIdentifiers {name: "Every 50 Rushing Yards", field_name: "rushing_yards_each_50"},
-    "32" => StatIdentifiers {name: "Every 100 Rushing Yards", field_name: "rushing_yards_each_100"},
-    "33" => StatIdentifiers {name: "Every 5 Rush Attempts", field_name: "rushing_attempts_each_5"},
-    "34" => StatIdentifiers {name: "Every 10 Rush Attempts", field_name: "rushing_attempts_each_10"},
-    "35" => StatIdentifiers {name: "40+ Yard TD Rush Bonus", field_name: "rushing_touchdown_40_plus_bonus"},
-    "36" => StatIdentifiers {name: "50+ Yard TD Rush Bonus", field_name: "rushing_touchdown_50_plus_bonus"},
-    "37" => StatIdentifiers {name: "100-199 Yard Rushing Game", field_name: "rushing_100_to_199_yards"},
-    "38" => StatIdentifiers {name: "200+ Yard Rushing Game", field_name: "rushing_over_200_yards"},
-    
-    "42" => StatIdentifiers {name: "Receiving Yards", field_name:"receiving_yards"},
-    "43" => StatIdentifiers {name: "Receiving Touchdowns", field_name: "receiving_touchdowns"},
-    "44" => StatIdentifiers {name: "Receiving 2 Point Conversions", field_name: "receiving_two_point_conversions"},
-    "45" => StatIdentifiers {name: "40+ Yard TD Receiving Bonus", field_name: "receiving_touchdown_40_plus_bonus"},
-    "46" => StatIdentifiers {name: "50+ Yard TD Receiving Bonus", field_name: "receiving_touchdown_50_plus_bonus"},
-    "47" => StatIdentifiers {name: "Every 5 Receiving Yards", field_name: "receiving_yards_each_5"},
-    "48" => StatIdentifiers {name: "Every 10 Receiving Yards", field_name: "receiving_yards_each_10"},
-    "49" => StatIdentifiers {name: "Every 20 Receiving Yards", field_name: "receiving_yards_each_20"},
-    "50" => StatIdentifiers {name: "Every 25 Receiving Yards", field_name: "receiving_yards_each_25"},
-    "51" => StatIdentifiers {name: "Every 50 Receiving Yards", field_name: "receiving_yards_each_50"},
-    "52" => StatIdentifiers {name: "Every 100 Receiving Yards", field_name: "receiving_yards_each_100"},
-    "53" => StatIdentifiers {name: "Receptions", field_name: "receptions"},
-    "54" => StatIdentifiers {name: "Every 5 Receptions", field_name: "receptions_each_5"},
-    "55" => StatIdentifiers {name: "Every 10 Receptions", field_name: "receptions_each_10"},
+impl StatIdentifiers {
+    pub fn new(
+        name: &'static str,
+        field_name: &'static str,
+        category: StatCategory,
+        unit: StatUnit,
+        derived: bool,
+    ) -> StatIdentifiers {
+        StatIdentifiers {
+            name,
+            field_name,
+            category,
+            unit,
+            derived,
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    pub fn field_name(&self) -> &'static str {
+        self.field_name
+    }
+    pub fn category(&self) -> StatCategory {
+        self.category
+    }
+    pub fn unit(&self) -> StatUnit {
+        self.unit
+    }
+    pub fn is_derived(&self) -> bool {
+        self.derived
+    }
+}
 
-    "56" => StatIdentifiers {name: "100-199 Yard Receiving Game", field_name: "receiving_100_to_199_yards"},
-    "57" => StatIdentifiers {name: "200+ Yard Receiving Game", field_name: "receiving_over_200_yards"},
-    "58" => StatIdentifiers {name: "Receiving Targets", field_name: "receiving_targets"},
-    
-    "63" => StatIdentifiers {name: "Fumble Recovered for Touchdown", field_name:"offensive_fumble_recovered_for_touchdown"},
-    "64" => StatIdentifiers {name: "Sacked", field_name: "sacked"},
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum StatCategory {
+    Passing,
+    Rushing,
+    Receiving,
+    Kicking,
+    /// Team defense and special teams.
+    Defense,
+    /// Individual defensive players (IDP).
+    IndividualDefense,
+    Punting,
+    HeadCoach,
+    /// Fumbles, returns and other stats that don't belong to one phase of the game.
+    #[default]
+    Miscellaneous,
+}
 
-    "68" => StatIdentifiers {name: "Total Fumbles", field_name: "total_fumbles"},
-    
-    "72" => StatIdentifiers {name: "Fumbles Lost", field_name: "fumbles_lost"},
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum StatUnit {
+    #[default]
+    Count,
+    Yards,
+    Points,
+    Percentage,
+    /// An average per attempt, reception or other play, e.g. yards per rush. Like percentages,
+    /// rates can't be added up across games.
+    Rate,
+}
 
-    "74" => StatIdentifiers {name: "Field Goals Made From 50+ Yards", field_name: "field_goals_made_50_plus"},
-    "77" => StatIdentifiers {name: "Field Goals Made From Between 40 and 49 Yards", field_name: "field_goals_made_40_to_49"},
-    "78" => StatIdentifiers {name: "Field Goals Attempted From 40 to 49 Yards", field_name: "field_goals_attempted_40_to_49"},
-    "79" => StatIdentifiers {name: "Field Goals Missed From 40 to 49 Yards", field_name: "field_goals_missed_40_to_49"},
-    "80" => StatIdentifiers {name: "Field Goals Made From <40+ Yards", field_name: "field_goals_made_under_40"},
-    "81" => StatIdentifiers {name: "Field Goals Attempted From <40+ Yards", field_name: "field_goals_attempted_under_40"},
-    "82" => StatIdentifiers {name: "Field Goals Missed From <40+ Yards", field_name: "field_goals_missed_under_40"},
-    "83" => StatIdentifiers {name: "Field Goals Made", field_name: "field_goals_made"},
-    "84" => StatIdentifiers {name: "Field Goals Attempted", field_name: "field_goals_attempted"},
-    "85" => StatIdentifiers {name: "Field Goals Missed", field_name: "field_goals_missed"},
-    "86" => StatIdentifiers {name: "Extra Points Made", field_name: "extra_points_made"},
-    "87" => StatIdentifiers {name: "Extra Points Attempted", field_name: "extra_points_attempted"},
-    "88" => StatIdentifiers {name: "Extra Points Missed", field_name: "extra_points_missed"},
+/// A sport ESPN runs fantasy leagues for. Only football is supported so far.
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Sport {
+    #[default]
+    Football,
+}
 
-    "89" => StatIdentifiers {name: "Defense Allowed 0 Points", field_name: "defense_0_points_allowed"},
-    "90" => StatIdentifiers {name: "Defense Allowed 1 to 6 Points", field_name: "defense_1_to_6_points_allowed"},
-    "91" => StatIdentifiers {name: "Defense Allowed 7 to 13 Points", field_name: "defense_7_to_13_points_allowed"},
-    "92" => StatIdentifiers {name: "Defense Allowed 14 to 17 Points", field_name: "defense_14_to_17_points_allowed"},
-    "93" => StatIdentifiers {name: "Defense Blocked Kicks for Touchdowns", field_name: "defense_blocked_kick_for_touchdowns"},
-    "94" => StatIdentifiers {name: "Fumble or INT Return for Touchdown", field_name: "defensive_fumble_or_int_return_for_touchdown"},
-    "95" => StatIdentifiers {name: "Defensive Interceptions", field_name: "defensive_interceptions"},
-    "96" => StatIdentifiers {name: "Defensive Fumbles Recovered", field_name: "defensive_fumbles_recovered"},
-    "97" => StatIdentifiers {name: "Defensive Blocked Kicks", field_name: "defensive_blocked_kicks"},
-    "98" => StatIdentifiers {name: "Defensive Safeties", field_name: "defensive_safeties"},
-    "99" => StatIdentifiers {name: "Defensive Sacks", field_name: "defensive_sacks"},
-    "100" => StatIdentifiers {name: "Defensive Half Sacks", field_name: "defensive_half_sacks"},
+impl Sport {
+    pub fn stat_catalog(&self) -> StatCatalog {
+        match self {
+            Sport::Football => StatCatalog { map: &STAT_ID_MAP },
+        }
+    }
+}
 
-    "101" => StatIdentifiers {name: "Kickoffs Returned for Touchdown", field_name: "kickoff_return_touchdown"},
-    "102" => StatIdentifiers {name: "Punts Returned for Touchdown", field_name: "punt_return_touchdown"},
-    "103" => StatIdentifiers {name: "Fumbles Returned for Touchdown", field_name: "fumble_return_touchdown"},
-    "104" => StatIdentifiers {name: "Interceptions Returned for Touchdown", field_name: "interception_return_touchdown"},
-    "106" => StatIdentifiers {name: "Forced Fumbles", field_name: "forced_fumbles"},
-    "107" => StatIdentifiers {name: "Assisted Tackles", field_name: "assisted_tackles"},
-    "108" => StatIdentifiers {name: "Solo Tackles", field_name: "solo_tackles"},
-    "109" => StatIdentifiers {name: "Total Tackles", field_name: "total_tackles"},
-    "110" => StatIdentifiers {name: "Every 3 Tackles", field_name: "tackles_each_3"},
-    "111" => StatIdentifiers {name: "Every 5 Tackles", field_name: "tackles_each_5"},
-    "112" => StatIdentifiers {name: "Stuffs", field_name: "stuffs"},
-    "113" => StatIdentifiers {name: "Passes Defended", field_name: "passes_defended"},
-    "114" => StatIdentifiers {name: "Kickoff Return Yards", field_name: "kickoff_return_yards"},
-    "115" => StatIdentifiers {name: "Punt Return Yards", field_name: "punt_return_yards"},
-    "116" => StatIdentifiers {name: "Every 10 Kickoff Return Yards", field_name: "kickoff_return_yards_each_10"},
-    "117" => StatIdentifiers {name: "Every 25 Kickoff Return Yards", field_name: "kickoff_return_yards_each_25"},
-    "118" => StatIdentifiers {name: "Every 10 Punt Return Yards", field_name: "punt_return_yards_each_10"},
-    "119" => StatIdentifiers {name: "Every 25 Punt Return Yards", field_name: "punt_return_yards_each_25"},
-    "120" => StatIdentifiers {name: "Points Allowed", field_name: "points_allowed"},
-    "121" => StatIdentifiers {name: "Defense Allowed 18 to 21 Points", field_name: "defense_18_to_21_points_allowed"},
-    "122" => StatIdentifiers {name: "Defense Allowed 22 to 27 Points", field_name: "defense_22_to_27_points_allowed"},
-    "123" => StatIdentifiers {name: "Defense Allowed 28 to 34 Points", field_name: "defense_28_to_34_points_allowed"},
-    "124" => StatIdentifiers {name: "Defense Allowed 35 to 45 Points", field_name: "defense_35_to_45_points_allowed"},
-    "125" => StatIdentifiers {name: "Defense Allowed 46+ Points", field_name: "defense_46_plus_points_allowed"},
-    "128" => StatIdentifiers {name: "Defensive Sacks", field_name: "defensive_sacks"},
-    "129" => StatIdentifiers {name: "Defense Allowed 100 to 199 Yards", field_name: "defense_100_to_199_yards_allowed"},
-    "130" => StatIdentifiers {name: "Defense Allowed 200 to 299 Yards", field_name: "defense_200_to_299_yards_allowed"},
-    "131" => StatIdentifiers {name: "Defense Allowed 300 to 349 Yards", field_name: "defense_300_to_349_yards_allowed"},
-    "132" => StatIdentifiers {name: "Defense Allowed 350 to 399 Yards", field_name: "defense_350_to_399_yards_allowed"},
-    "133" => StatIdentifiers {name: "Defense Allowed 400 to 449 Yards", field_name: "defense_400_to_449_yards_allowed"},
-    "134" => StatIdentifiers {name: "Defense Allowed 450 to 499 Yards", field_name: "defense_450_to_499_yards_allowed"},
-    "135" => StatIdentifiers {name: "Defense Allowed 500 to 549 Yards", field_name: "defense_500_to_549_yards_allowed"},
-    "136" => StatIdentifiers {name: "Defense Allowed More than 550 Yards", field_name: "defense_over_550_yards_allowed"},
-    "138" => StatIdentifiers {name: "Net Punts", field_name: "punts_net"},
-    "139" => StatIdentifiers {name: "Punt Yards", field_name: "punt_yards"},
-    "140" => StatIdentifiers {name: "Punts Inside the 10", field_name: "punts_inside_10"},
-    "141" => StatIdentifiers {name: "Punts Inside the 20", field_name: "punts_inside_20"},
-    "142" => StatIdentifiers {name: "Punts Blocked", field_name: "punts_blocked"},
-    "143" => StatIdentifiers {name: "Punts Returned", field_name: "punts_returned"},
-    "144" => StatIdentifiers {name: "Punt Return Yards", field_name: "punt_return_yards"},
-    "145" => StatIdentifiers {name: "Punt Touchbacks", field_name: "punts_touchback"},
-    "146" => StatIdentifiers {name: "Punts Fair Caught", field_name: "punts_fair_caught"},
-    
-    "148" => StatIdentifiers {name: "Punt Average 44+", field_name: "punt_average_over_44"},
-    "149" => StatIdentifiers {name: "Punt Average 42.0-43.9", field_name: "punt_average_42_to_44"},
-    "150" => StatIdentifiers {name: "Punt Average 40.0-41.9", field_name: "punt_average_40_to_42"},
-    "151" => StatIdentifiers {name: "Punt Average 38.0-39.9", field_name: "punt_average_38_to_40"},
-    "152" => StatIdentifiers {name: "Punt Average 36.0-37.9", field_name: "punt_average_36_to_38"},
-    "153" => StatIdentifiers {name: "Punt Average 34.0-35.9", field_name: "punt_average_34_to_36"},
-    "154" => StatIdentifiers {name: "Punt Average 33.9 or less", field_name: "punt_average_below_34"},
-    "155" => StatIdentifiers {name: "Team Win", field_name: "team_win"},
-    "156" => StatIdentifiers {name: "Team Loss", field_name: "team_loss"},
-    "158" => StatIdentifiers {name: "Team Points Scored", field_name: "team_points_scored"},
-    "161" => StatIdentifiers {name: "Win Margin 25+", field_name: "win_margin_25_plus"},
-    "162" => StatIdentifiers {name: "Win Margin 20-24", field_name: "win_margin_20_to_24"},
-    "163" => StatIdentifiers {name: "Win Margin 15-19", field_name: "win_margin_15_to_19"},
-    "164" => StatIdentifiers {name: "Win Margin 10-14", field_name: "win_margin_10_to_14"},
-    "165" => StatIdentifiers {name: "Win Margin 5-9", field_name: "win_margin_5_to_9"},
-    "166" => StatIdentifiers {name: "Win Margin 1-4", field_name: "win_margin_1_to_4"},
-    "167" => StatIdentifiers {name: "Loss Margin 1-4", field_name: "loss_margin_1_to_4"},
-    "168" => StatIdentifiers {name: "Loss Margin 5-9", field_name: "loss_margin_5_to_9"},
-    "169" => StatIdentifiers {name: "Loss Margin 10-14", field_name: "loss_margin_10_to_14"},
-    "170" => StatIdentifiers {name: "Loss Margin 15-19", field_name: "loss_margin_15_to_19"},
-    "171" => StatIdentifiers {name: "Loss Margin 20-24", field_name: "loss_margin_20_to_24"},
-    "172" => StatIdentifiers {name: "Loss Margin 25+", field_name: "loss_margin_25_plus"},
-    
+/// The stats built into this crate for a sport. `StatId` lookups also consult the `registry`.
+///
+/// The catalog only names the stats whose meaning is known, so it has gaps: ESPN sends ids such
+/// as 22, 40-41, 61, 65-67, 69-71, 126, 137, 147, 159-160, 173-197, 204-210, 215-217 and 220
+/// that aren't in it. Those are reported as "Unknown"; the `find_unmapped_stats` example helps
+/// work out what they count, and the `registry` can name them.
+#[derive(Clone, Copy)]
+pub struct StatCatalog {
+    map: &'static phf::Map<&'static str, StatIdentifiers>,
+}
 
-    "198" => StatIdentifiers {name: "Field Goals Made From 50 to 59 Yards", field_name: "field_goals_made_50_to_59"},
-    "199" => StatIdentifiers {name: "Field Goals Attempted From 50 to 59 Yards", field_name: "field_goals_attempted_50_to_59"},
-    "200" => StatIdentifiers {name: "Field Goals Missed From 50 to 59 Yards", field_name: "field_goals_missed_50_to_59"},
-    "201" => StatIdentifiers {name: "Field Goals Made From 60+ Yards", field_name: "field_goals_made_60_plus"},
-    "202" => StatIdentifiers {name: "Field Goals Attempted From 60+ Yards", field_name: "field_goals_attempted_60_plus"},
-    "203" => StatIdentifiers {name: "Field Goals Missed From 60+ Yards", field_name: "field_goals_missed_60_plus"},
+impl StatCatalog {
+    pub fn get(&self, id: &StatId) -> Option<&'static StatIdentifiers> {
+        self.map.get(id.0.to_string().as_str())
+    }
+    pub fn from_field_name(&self, field_name: &str) -> Option<StatId> {
+        self.map
+            .entries()
+            .find(|(_, ids)| ids.field_name == field_name)
+            .and_then(|(id, _)| id.parse().ok())
+            .map(StatId)
+    }
+    /// Every stat in the catalog, ordered by id.
+    pub fn iter(&self) -> Vec<(StatId, &'static StatIdentifiers)> {
+        let mut stats = self
+            .map
+            .entries()
+            .filter_map(|(id, ids)| Some((StatId(id.parse().ok()?), ids)))
+            .collect::<Vec<_>>();
+        stats.sort_by_key(|(id, _)| id.0);
+        stats
+    }
+    pub fn in_category(&self, category: StatCategory) -> Vec<(StatId, &'static StatIdentifiers)> {
+        self.iter()
+            .into_iter()
+            .filter(|(_, ids)| ids.category == category)
+            .collect()
+    }
+}
 
-    "211" => StatIdentifiers {name: "Passing First Downs", field_name: "passing_first_downs"},
-    "212" => StatIdentifiers {name: "Rushing First Downs", field_name: "rushing_first_downs"},
-    "213" => StatIdentifiers {name: "Receiving First Downs", field_name: "receiving_first_downs"},
-    "214" => StatIdentifiers {name: "FG Made Yards", field_name: "field_goal_made_yards"},
-    "218" => StatIdentifiers {name: "Every 10 FG Made Yards", field_name: "field_goal_made_yards_each_10"},
-    "219" => StatIdentifiers {name: "Every 20 FG Made Yards", field_name: "field_goal_made_yards_each_20"},
-    "221" => StatIdentifiers {name: "Every 50 FG Made Yards", field_name: "field_goal_made_yards_each_50"},
+// Adapted from https://github.com/mkreiser/ESPN-Fantasy-Football-API/blob/main/src/player-stats/player-stats.js
+// use the `find_unmapped_stats` example to locate stats to add from your league for a given week
+static STAT_ID_MAP: phf::Map<&'static str, StatIdentifiers> = phf_map! {
+    "0" => StatIdentifiers {name: "Pass Attempts", field_name: "pass_attempts", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "1" => StatIdentifiers {name: "Completed Passes", field_name: "passing_completions", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "2" => StatIdentifiers {name: "Incomplete Passes", field_name: "passing_incompletions", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "3" => StatIdentifiers {name: "Passing Yards", field_name: "passing_yards", category: StatCategory::Passing, unit: StatUnit::Yards, derived: false},
+    "4" => StatIdentifiers {name: "Passing Touchdowns", field_name: "passing_touchdowns", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "5" => StatIdentifiers {name: "Every 5 Passing Yards", field_name: "passing_yards_each_5", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "6" => StatIdentifiers {name: "Every 10 Passing Yards", field_name: "passing_yards_each_10", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "7" => StatIdentifiers {name: "Every 20 Passing Yards", field_name: "passing_yards_each_20", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "8" => StatIdentifiers {name: "Every 25 Passing Yards", field_name: "passing_yards_each_25", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "9" => StatIdentifiers {name: "Every 50 Passing Yards", field_name: "passing_yards_each_50", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "10" => StatIdentifiers {name: "Every 100 Passing Yards", field_name: "passing_yards_each_100", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "11" => StatIdentifiers {name: "Every 5 Passing Completions", field_name: "passing_completions_each_5", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "12" => StatIdentifiers {name: "Every 10 Passing Completions", field_name: "passing_completions_each_10", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "13" => StatIdentifiers {name: "Every 5 Passing Incompletions", field_name: "passing_incompletions_each_5", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "14" => StatIdentifiers {name: "Every 10 Passing Incompletions", field_name: "passing_incompletions_each_10", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "15" => StatIdentifiers {name: "40+ Yard TD Pass Bonus", field_name: "passing_touchdown_40_plus_bonus", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "16" => StatIdentifiers {name: "50+ Yard TD Pass Bonus", field_name: "passing_touchdown_50_plus_bonus", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "17" => StatIdentifiers {name: "300-399 yard passing game", field_name: "passing_300_to_399_yard_game", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "18" => StatIdentifiers {name: "400+ yard passing game", field_name: "passing_over_400_yards", category: StatCategory::Passing, unit: StatUnit::Count, derived: true},
+    "19" => StatIdentifiers {name: "Passing 2 Point Conversions", field_name: "passing_two_point_conversions", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "20" => StatIdentifiers {name: "Passing Interceptions", field_name: "passing_interceptions", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "21" => StatIdentifiers {name: "Passing Completion Percentage", field_name: "passing_completion_percentage", category: StatCategory::Passing, unit: StatUnit::Percentage, derived: true},
+    "23" => StatIdentifiers {name: "Rushing Attempts", field_name: "rushing_attempts", category: StatCategory::Rushing, unit: StatUnit::Count, derived: false},
+    "24" => StatIdentifiers {name: "Rushing Yards", field_name: "rushing_yards", category: StatCategory::Rushing, unit: StatUnit::Yards, derived: false},
+    "25" => StatIdentifiers {name: "Rushing Touchdowns", field_name: "rushing_touchdowns", category: StatCategory::Rushing, unit: StatUnit::Count, derived: false},
+    "26" => StatIdentifiers {name: "Rushing 2 Point Conversions", field_name: "rushing_two_point_conversions", category: StatCategory::Rushing, unit: StatUnit::Count, derived: false},
+    "27" => StatIdentifiers {name: "Every 5 Rushing Yards", field_name: "rushing_yards_each_5", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "28" => StatIdentifiers {name: "Every 10 Rushing Yards", field_name: "rushing_yards_each_10", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "29" => StatIdentifiers {name: "Every 20 Rushing Yards", field_name: "rushing_yards_each_20", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "30" => StatIdentifiers {name: "Every 25 Rushing Yards", field_name: "rushing_yards_each_25", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "31" => StatIdentifiers {name: "Every 50 Rushing Yards", field_name: "rushing_yards_each_50", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "32" => StatIdentifiers {name: "Every 100 Rushing Yards", field_name: "rushing_yards_each_100", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "33" => StatIdentifiers {name: "Every 5 Rush Attempts", field_name: "rushing_attempts_each_5", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "34" => StatIdentifiers {name: "Every 10 Rush Attempts", field_name: "rushing_attempts_each_10", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "35" => StatIdentifiers {name: "40+ Yard TD Rush Bonus", field_name: "rushing_touchdown_40_plus_bonus", category: StatCategory::Rushing, unit: StatUnit::Count, derived: false},
+    "36" => StatIdentifiers {name: "50+ Yard TD Rush Bonus", field_name: "rushing_touchdown_50_plus_bonus", category: StatCategory::Rushing, unit: StatUnit::Count, derived: false},
+    "37" => StatIdentifiers {name: "100-199 Yard Rushing Game", field_name: "rushing_100_to_199_yards", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "38" => StatIdentifiers {name: "200+ Yard Rushing Game", field_name: "rushing_over_200_yards", category: StatCategory::Rushing, unit: StatUnit::Count, derived: true},
+    "39" => StatIdentifiers {name: "Rushing Yards Per Attempt", field_name: "rushing_yards_per_attempt", category: StatCategory::Rushing, unit: StatUnit::Rate, derived: true},
+    "42" => StatIdentifiers {name: "Receiving Yards", field_name: "receiving_yards", category: StatCategory::Receiving, unit: StatUnit::Yards, derived: false},
+    "43" => StatIdentifiers {name: "Receiving Touchdowns", field_name: "receiving_touchdowns", category: StatCategory::Receiving, unit: StatUnit::Count, derived: false},
+    "44" => StatIdentifiers {name: "Receiving 2 Point Conversions", field_name: "receiving_two_point_conversions", category: StatCategory::Receiving, unit: StatUnit::Count, derived: false},
+    "45" => StatIdentifiers {name: "40+ Yard TD Receiving Bonus", field_name: "receiving_touchdown_40_plus_bonus", category: StatCategory::Receiving, unit: StatUnit::Count, derived: false},
+    "46" => StatIdentifiers {name: "50+ Yard TD Receiving Bonus", field_name: "receiving_touchdown_50_plus_bonus", category: StatCategory::Receiving, unit: StatUnit::Count, derived: false},
+    "47" => StatIdentifiers {name: "Every 5 Receiving Yards", field_name: "receiving_yards_each_5", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "48" => StatIdentifiers {name: "Every 10 Receiving Yards", field_name: "receiving_yards_each_10", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "49" => StatIdentifiers {name: "Every 20 Receiving Yards", field_name: "receiving_yards_each_20", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "50" => StatIdentifiers {name: "Every 25 Receiving Yards", field_name: "receiving_yards_each_25", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "51" => StatIdentifiers {name: "Every 50 Receiving Yards", field_name: "receiving_yards_each_50", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "52" => StatIdentifiers {name: "Every 100 Receiving Yards", field_name: "receiving_yards_each_100", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "53" => StatIdentifiers {name: "Receptions", field_name: "receptions", category: StatCategory::Receiving, unit: StatUnit::Count, derived: false},
+    "54" => StatIdentifiers {name: "Every 5 Receptions", field_name: "receptions_each_5", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "55" => StatIdentifiers {name: "Every 10 Receptions", field_name: "receptions_each_10", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "56" => StatIdentifiers {name: "100-199 Yard Receiving Game", field_name: "receiving_100_to_199_yards", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "57" => StatIdentifiers {name: "200+ Yard Receiving Game", field_name: "receiving_over_200_yards", category: StatCategory::Receiving, unit: StatUnit::Count, derived: true},
+    "58" => StatIdentifiers {name: "Receiving Targets", field_name: "receiving_targets", category: StatCategory::Receiving, unit: StatUnit::Count, derived: false},
+    "59" => StatIdentifiers {name: "Receiving Yards After Catch", field_name: "receiving_yards_after_catch", category: StatCategory::Receiving, unit: StatUnit::Yards, derived: false},
+    "60" => StatIdentifiers {name: "Receiving Yards Per Reception", field_name: "receiving_yards_per_reception", category: StatCategory::Receiving, unit: StatUnit::Rate, derived: true},
+    "62" => StatIdentifiers {name: "2 Point Conversions", field_name: "two_point_conversions", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: true},
+    "63" => StatIdentifiers {name: "Fumble Recovered for Touchdown", field_name: "offensive_fumble_recovered_for_touchdown", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: false},
+    "64" => StatIdentifiers {name: "Sacked", field_name: "sacked", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "68" => StatIdentifiers {name: "Total Fumbles", field_name: "total_fumbles", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: true},
+    "72" => StatIdentifiers {name: "Fumbles Lost", field_name: "fumbles_lost", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: false},
+    "73" => StatIdentifiers {name: "Turnovers", field_name: "turnovers", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: true},
+    "74" => StatIdentifiers {name: "Field Goals Made From 50+ Yards", field_name: "field_goals_made_50_plus", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "75" => StatIdentifiers {name: "Field Goals Attempted From 50+ Yards", field_name: "field_goals_attempted_50_plus", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "76" => StatIdentifiers {name: "Field Goals Missed From 50+ Yards", field_name: "field_goals_missed_50_plus", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
+    "77" => StatIdentifiers {name: "Field Goals Made From Between 40 and 49 Yards", field_name: "field_goals_made_40_to_49", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "78" => StatIdentifiers {name: "Field Goals Attempted From 40 to 49 Yards", field_name: "field_goals_attempted_40_to_49", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "79" => StatIdentifiers {name: "Field Goals Missed From 40 to 49 Yards", field_name: "field_goals_missed_40_to_49", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
+    "80" => StatIdentifiers {name: "Field Goals Made From <40+ Yards", field_name: "field_goals_made_under_40", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "81" => StatIdentifiers {name: "Field Goals Attempted From <40+ Yards", field_name: "field_goals_attempted_under_40", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "82" => StatIdentifiers {name: "Field Goals Missed From <40+ Yards", field_name: "field_goals_missed_under_40", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
+    "83" => StatIdentifiers {name: "Field Goals Made", field_name: "field_goals_made", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "84" => StatIdentifiers {name: "Field Goals Attempted", field_name: "field_goals_attempted", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "85" => StatIdentifiers {name: "Field Goals Missed", field_name: "field_goals_missed", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
+    "86" => StatIdentifiers {name: "Extra Points Made", field_name: "extra_points_made", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "87" => StatIdentifiers {name: "Extra Points Attempted", field_name: "extra_points_attempted", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "88" => StatIdentifiers {name: "Extra Points Missed", field_name: "extra_points_missed", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
+    "89" => StatIdentifiers {name: "Defense Allowed 0 Points", field_name: "defense_0_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "90" => StatIdentifiers {name: "Defense Allowed 1 to 6 Points", field_name: "defense_1_to_6_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "91" => StatIdentifiers {name: "Defense Allowed 7 to 13 Points", field_name: "defense_7_to_13_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "92" => StatIdentifiers {name: "Defense Allowed 14 to 17 Points", field_name: "defense_14_to_17_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "93" => StatIdentifiers {name: "Defense Blocked Kicks for Touchdowns", field_name: "defense_blocked_kick_for_touchdowns", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "94" => StatIdentifiers {name: "Fumble or INT Return for Touchdown", field_name: "defensive_fumble_or_int_return_for_touchdown", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "95" => StatIdentifiers {name: "Defensive Interceptions", field_name: "defensive_interceptions", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "96" => StatIdentifiers {name: "Defensive Fumbles Recovered", field_name: "defensive_fumbles_recovered", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "97" => StatIdentifiers {name: "Defensive Blocked Kicks", field_name: "defensive_blocked_kicks", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "98" => StatIdentifiers {name: "Defensive Safeties", field_name: "defensive_safeties", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "99" => StatIdentifiers {name: "Defensive Sacks", field_name: "defensive_sacks", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "100" => StatIdentifiers {name: "Defensive Half Sacks", field_name: "defensive_half_sacks", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "101" => StatIdentifiers {name: "Kickoffs Returned for Touchdown", field_name: "kickoff_return_touchdown", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "102" => StatIdentifiers {name: "Punts Returned for Touchdown", field_name: "punt_return_touchdown", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "103" => StatIdentifiers {name: "Fumbles Returned for Touchdown", field_name: "fumble_return_touchdown", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "104" => StatIdentifiers {name: "Interceptions Returned for Touchdown", field_name: "interception_return_touchdown", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "105" => StatIdentifiers {name: "Defensive Touchdowns", field_name: "defensive_touchdowns", category: StatCategory::Defense, unit: StatUnit::Count, derived: false},
+    "106" => StatIdentifiers {name: "Forced Fumbles", field_name: "forced_fumbles", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: false},
+    "107" => StatIdentifiers {name: "Assisted Tackles", field_name: "assisted_tackles", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: false},
+    "108" => StatIdentifiers {name: "Solo Tackles", field_name: "solo_tackles", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: false},
+    "109" => StatIdentifiers {name: "Total Tackles", field_name: "total_tackles", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: true},
+    "110" => StatIdentifiers {name: "Every 3 Tackles", field_name: "tackles_each_3", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: true},
+    "111" => StatIdentifiers {name: "Every 5 Tackles", field_name: "tackles_each_5", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: true},
+    "112" => StatIdentifiers {name: "Stuffs", field_name: "stuffs", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: false},
+    "113" => StatIdentifiers {name: "Passes Defended", field_name: "passes_defended", category: StatCategory::IndividualDefense, unit: StatUnit::Count, derived: false},
+    "114" => StatIdentifiers {name: "Kickoff Return Yards", field_name: "kickoff_return_yards", category: StatCategory::Miscellaneous, unit: StatUnit::Yards, derived: false},
+    "115" => StatIdentifiers {name: "Punt Return Yards", field_name: "punt_return_yards", category: StatCategory::Miscellaneous, unit: StatUnit::Yards, derived: false},
+    "116" => StatIdentifiers {name: "Every 10 Kickoff Return Yards", field_name: "kickoff_return_yards_each_10", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: true},
+    "117" => StatIdentifiers {name: "Every 25 Kickoff Return Yards", field_name: "kickoff_return_yards_each_25", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: true},
+    "118" => StatIdentifiers {name: "Every 10 Punt Return Yards", field_name: "punt_return_yards_each_10", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: true},
+    "119" => StatIdentifiers {name: "Every 25 Punt Return Yards", field_name: "punt_return_yards_each_25", category: StatCategory::Miscellaneous, unit: StatUnit::Count, derived: true},
+    "120" => StatIdentifiers {name: "Points Allowed", field_name: "points_allowed", category: StatCategory::Defense, unit: StatUnit::Points, derived: false},
+    "121" => StatIdentifiers {name: "Defense Allowed 18 to 21 Points", field_name: "defense_18_to_21_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "122" => StatIdentifiers {name: "Defense Allowed 22 to 27 Points", field_name: "defense_22_to_27_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "123" => StatIdentifiers {name: "Defense Allowed 28 to 34 Points", field_name: "defense_28_to_34_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "124" => StatIdentifiers {name: "Defense Allowed 35 to 45 Points", field_name: "defense_35_to_45_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "125" => StatIdentifiers {name: "Defense Allowed 46+ Points", field_name: "defense_46_plus_points_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "127" => StatIdentifiers {name: "Yards Allowed", field_name: "yards_allowed", category: StatCategory::Defense, unit: StatUnit::Yards, derived: false},
+    "128" => StatIdentifiers {name: "Defense Allowed Less than 100 Yards", field_name: "defense_under_100_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "129" => StatIdentifiers {name: "Defense Allowed 100 to 199 Yards", field_name: "defense_100_to_199_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "130" => StatIdentifiers {name: "Defense Allowed 200 to 299 Yards", field_name: "defense_200_to_299_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "131" => StatIdentifiers {name: "Defense Allowed 300 to 349 Yards", field_name: "defense_300_to_349_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "132" => StatIdentifiers {name: "Defense Allowed 350 to 399 Yards", field_name: "defense_350_to_399_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "133" => StatIdentifiers {name: "Defense Allowed 400 to 449 Yards", field_name: "defense_400_to_449_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "134" => StatIdentifiers {name: "Defense Allowed 450 to 499 Yards", field_name: "defense_450_to_499_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "135" => StatIdentifiers {name: "Defense Allowed 500 to 549 Yards", field_name: "defense_500_to_549_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "136" => StatIdentifiers {name: "Defense Allowed More than 550 Yards", field_name: "defense_over_550_yards_allowed", category: StatCategory::Defense, unit: StatUnit::Count, derived: true},
+    "138" => StatIdentifiers {name: "Net Punts", field_name: "punts_net", category: StatCategory::Punting, unit: StatUnit::Count, derived: false},
+    "139" => StatIdentifiers {name: "Punt Yards", field_name: "punt_yards", category: StatCategory::Punting, unit: StatUnit::Yards, derived: false},
+    "140" => StatIdentifiers {name: "Punts Inside the 10", field_name: "punts_inside_10", category: StatCategory::Punting, unit: StatUnit::Count, derived: false},
+    "141" => StatIdentifiers {name: "Punts Inside the 20", field_name: "punts_inside_20", category: StatCategory::Punting, unit: StatUnit::Count, derived: false},
+    "142" => StatIdentifiers {name: "Punts Blocked", field_name: "punts_blocked", category: StatCategory::Punting, unit: StatUnit::Count, derived: false},
+    "143" => StatIdentifiers {name: "Punts Returned", field_name: "punts_returned", category: StatCategory::Punting, unit: StatUnit::Count, derived: false},
+    "144" => StatIdentifiers {name: "Punt Return Yards Allowed", field_name: "punt_return_yards_allowed", category: StatCategory::Punting, unit: StatUnit::Yards, derived: false},
+    "145" => StatIdentifiers {name: "Punt Touchbacks", field_name: "punts_touchback", category: StatCategory::Punting, unit: StatUnit::Count, derived: false},
+    "146" => StatIdentifiers {name: "Punts Fair Caught", field_name: "punts_fair_caught", category: StatCategory::Punting, unit: StatUnit::Count, derived: false},
+    "148" => StatIdentifiers {name: "Punt Average 44+", field_name: "punt_average_over_44", category: StatCategory::Punting, unit: StatUnit::Count, derived: true},
+    "149" => StatIdentifiers {name: "Punt Average 42.0-43.9", field_name: "punt_average_42_to_44", category: StatCategory::Punting, unit: StatUnit::Count, derived: true},
+    "150" => StatIdentifiers {name: "Punt Average 40.0-41.9", field_name: "punt_average_40_to_42", category: StatCategory::Punting, unit: StatUnit::Count, derived: true},
+    "151" => StatIdentifiers {name: "Punt Average 38.0-39.9", field_name: "punt_average_38_to_40", category: StatCategory::Punting, unit: StatUnit::Count, derived: true},
+    "152" => StatIdentifiers {name: "Punt Average 36.0-37.9", field_name: "punt_average_36_to_38", category: StatCategory::Punting, unit: StatUnit::Count, derived: true},
+    "153" => StatIdentifiers {name: "Punt Average 34.0-35.9", field_name: "punt_average_34_to_36", category: StatCategory::Punting, unit: StatUnit::Count, derived: true},
+    "154" => StatIdentifiers {name: "Punt Average 33.9 or less", field_name: "punt_average_below_34", category: StatCategory::Punting, unit: StatUnit::Count, derived: true},
+    "155" => StatIdentifiers {name: "Team Win", field_name: "team_win", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: false},
+    "156" => StatIdentifiers {name: "Team Loss", field_name: "team_loss", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: false},
+    "157" => StatIdentifiers {name: "Team Tie", field_name: "team_tie", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: false},
+    "158" => StatIdentifiers {name: "Team Points Scored", field_name: "team_points_scored", category: StatCategory::HeadCoach, unit: StatUnit::Points, derived: false},
+    "161" => StatIdentifiers {name: "Win Margin 25+", field_name: "win_margin_25_plus", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "162" => StatIdentifiers {name: "Win Margin 20-24", field_name: "win_margin_20_to_24", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "163" => StatIdentifiers {name: "Win Margin 15-19", field_name: "win_margin_15_to_19", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "164" => StatIdentifiers {name: "Win Margin 10-14", field_name: "win_margin_10_to_14", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "165" => StatIdentifiers {name: "Win Margin 5-9", field_name: "win_margin_5_to_9", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "166" => StatIdentifiers {name: "Win Margin 1-4", field_name: "win_margin_1_to_4", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "167" => StatIdentifiers {name: "Loss Margin 1-4", field_name: "loss_margin_1_to_4", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "168" => StatIdentifiers {name: "Loss Margin 5-9", field_name: "loss_margin_5_to_9", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "169" => StatIdentifiers {name: "Loss Margin 10-14", field_name: "loss_margin_10_to_14", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "170" => StatIdentifiers {name: "Loss Margin 15-19", field_name: "loss_margin_15_to_19", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "171" => StatIdentifiers {name: "Loss Margin 20-24", field_name: "loss_margin_20_to_24", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "172" => StatIdentifiers {name: "Loss Margin 25+", field_name: "loss_margin_25_plus", category: StatCategory::HeadCoach, unit: StatUnit::Count, derived: true},
+    "198" => StatIdentifiers {name: "Field Goals Made From 50 to 59 Yards", field_name: "field_goals_made_50_to_59", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "199" => StatIdentifiers {name: "Field Goals Attempted From 50 to 59 Yards", field_name: "field_goals_attempted_50_to_59", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "200" => StatIdentifiers {name: "Field Goals Missed From 50 to 59 Yards", field_name: "field_goals_missed_50_to_59", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "201" => StatIdentifiers {name: "Field Goals Made From 60+ Yards", field_name: "field_goals_made_60_plus", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "202" => StatIdentifiers {name: "Field Goals Attempted From 60+ Yards", field_name: "field_goals_attempted_60_plus", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "203" => StatIdentifiers {name: "Field Goals Missed From 60+ Yards", field_name: "field_goals_missed_60_plus", category: StatCategory::Kicking, unit: StatUnit::Count, derived: false},
+    "211" => StatIdentifiers {name: "Passing First Downs", field_name: "passing_first_downs", category: StatCategory::Passing, unit: StatUnit::Count, derived: false},
+    "212" => StatIdentifiers {name: "Rushing First Downs", field_name: "rushing_first_downs", category: StatCategory::Rushing, unit: StatUnit::Count, derived: false},
+    "213" => StatIdentifiers {name: "Receiving First Downs", field_name: "receiving_first_downs", category: StatCategory::Receiving, unit: StatUnit::Count, derived: false},
+    "214" => StatIdentifiers {name: "FG Made Yards", field_name: "field_goal_made_yards", category: StatCategory::Kicking, unit: StatUnit::Yards, derived: false},
+    "218" => StatIdentifiers {name: "Every 10 FG Made Yards", field_name: "field_goal_made_yards_each_10", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
+    "219" => StatIdentifiers {name: "Every 20 FG Made Yards", field_name: "field_goal_made_yards_each_20", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
+    "221" => StatIdentifiers {name: "Every 50 FG Made Yards", field_name: "field_goal_made_yards_each_50", category: StatCategory::Kicking, unit: StatUnit::Count, derived: true},
 };
 
 #[cfg(test)]
 mod test {
     use super::*;
     #[test]
-    fn position_id_converts_to_string() {
-        assert_eq!(PositionId(0u64).to_string(), "QB");
-        assert_eq!(PositionId(16u64).to_string(), "D/ST");
-        assert_eq!(PositionId(24u64).to_string(), "Unknown");
-    }
-    #[test]
     fn pro_team_id_converts_to_string() {
-        assert_eq!(ProTeamId(0i8).to_name(), "Unknown?");
+        assert_eq!(ProTeamId(0i8).to_name(), "Unknown");
         assert_eq!(ProTeamId(16i8).to_name(), "Minnesota Vikings");
         assert_eq!(ProTeamId(24i8).to_name(), "Los Angeles Chargers");
     }
@@ -325,6 +435,27 @@ mod test {
         let stat = StatId(72u64);
         assert_eq!(stat.to_name(), "Fumbles Lost");
         assert_eq!(stat.to_field_name(), "fumbles_lost");
-        assert_eq!(StatId(1u64).to_name(), "Unknown")
+        assert_eq!(StatId(1000u64).to_name(), "Unknown")
+    }
+    #[test]
+    fn stat_field_names_round_trip() {
+        let stats = StatId::all();
+        assert!(stats.windows(2).all(|w| w[0].0 .0 < w[1].0 .0));
+        for (id, ids) in stats {
+            assert_eq!(StatId::from_field_name(ids.field_name()), Some(id));
+        }
+        assert_eq!(StatId::from_field_name("not_a_stat"), None);
+    }
+    #[test]
+    fn stat_categories_and_units() {
+        assert_eq!(StatId(3).category(), StatCategory::Passing);
+        assert_eq!(StatId(3).unit(), StatUnit::Yards);
+        assert!(StatId(6).is_derived());
+        assert_eq!(StatId(128).to_name(), "Defense Allowed Less than 100 Yards");
+        assert!(!StatId(1000).is_known());
+        assert!(!StatId(22).is_known());
+        assert_eq!(StatId(21).unit(), StatUnit::Percentage);
+        assert_eq!(StatId(39).unit(), StatUnit::Rate);
+        assert_eq!(StatId(60).unit(), StatUnit::Rate);
     }
 }
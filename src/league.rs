@@ -4,8 +4,12 @@ use std::collections::HashMap;
 use crate::members::MemberId;
 
 use super::{
-    id_maps::{PositionId, StatId},
+    id_maps::StatId,
+    matchup::Matchup,
+    position::{LineupSlot, Position},
+    raw::Raw,
     team::TeamId,
+    timestamp::Timestamp,
 };
 
 #[derive(Deserialize, Debug, Serialize)]
@@ -23,6 +27,18 @@ pub struct LeagueResponse {
     pub teams: Option<Vec<TeamInfo>>,
 }
 
+impl Raw<LeagueResponse> {
+    /// The league's settings along with the JSON ESPN sent for them, if `mSettings` was requested.
+    pub fn settings(&self) -> Option<Raw<LeagueSettings>> {
+        self.at("/settings")
+    }
+
+    /// The league's status along with the JSON ESPN sent for it, if `mStatus` was requested.
+    pub fn status(&self) -> Option<Raw<LeagueStatus>> {
+        self.at("/status")
+    }
+}
+
 #[derive(Deserialize, Debug, Serialize)]
 pub struct TeamInfo {
     #[serde(rename = "abbrev")]
@@ -36,7 +52,7 @@ pub struct TeamInfo {
 #[derive(Deserialize, Debug, Serialize)]
 pub struct LeagueStatus {
     #[serde(rename = "activatedDate")]
-    pub activated_date: Option<u64>,
+    pub activated_date: Option<Timestamp>,
     #[serde(rename = "createdAsLeagueType")]
     pub created_as_league_type: Option<i8>,
     #[serde(rename = "creationInfo")]
@@ -70,17 +86,24 @@ pub struct LeagueStatus {
     #[serde(rename = "previousSeasons")]
     pub previous_seasons: Option<Vec<u16>>,
     #[serde(rename = "standingsUpdateDate")]
-    pub standings_update_date: Option<u64>,
+    pub standings_update_date: Option<Timestamp>,
     #[serde(rename = "teamsJoined")]
     pub teams_joined: Option<i8>,
     #[serde(rename = "transactionScoringPeriod")]
     pub transaction_scoring_period: Option<i8>,
     #[serde(rename = "waiverLastExecutionDate")]
-    pub waiver_last_execution_date: Option<u64>,
+    pub waiver_last_execution_date: Option<Timestamp>,
     #[serde(rename = "waiverProcessStatus")]
     pub waiver_process_status: Option<HashMap<String, i8>>,
 }
 
+impl LeagueStatus {
+    /// When waivers were last processed, if they ever have been this season.
+    pub fn waiver_last_executed(&self) -> Option<Timestamp> {
+        self.waiver_last_execution_date.filter(|d| d.is_set())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LeagueUpdateInfo {
     #[serde(rename = "clientAddress")]
@@ -180,8 +203,8 @@ pub struct DraftSettings {
     #[serde(rename = "auctionBudget")]
     pub auction_budget: f32,
     #[serde(rename = "availableDate")]
-    pub available_date: u64,
-    pub date: u64,
+    pub available_date: Timestamp,
+    pub date: Timestamp,
     #[serde(rename = "isTradingEnabled")]
     pub is_trading_enabled: bool,
     #[serde(rename = "keeperCount")]
@@ -232,20 +255,34 @@ pub struct RosterSettings {
     pub lineup_locktime_type: String,
     /// The number of players that can be active in a given position for the purposes of scoring
     #[serde(rename = "lineupSlotCounts")]
-    pub lineup_slot_counts: HashMap<PositionId, i8>,
+    pub lineup_slot_counts: HashMap<LineupSlot, i8>,
     #[serde(rename = "lineupSlotStatLimits")]
     pub lineup_slot_stat_limits: HashMap<u8, u8>,
     #[serde(rename = "moveLimit")]
     pub move_limit: i32,
     /// The number of players of a given type that can be rostered at once
     #[serde(rename = "positionLimits")]
-    pub position_limits: HashMap<PositionId, i8>,
+    pub position_limits: HashMap<Position, i8>,
     #[serde(rename = "rosterLocktimeType")]
     pub roster_locktime_type: String,
     #[serde(rename = "universeIds")]
     pub universe_ids: Vec<i8>,
 }
 
+impl RosterSettings {
+    /// How many players a team can roster outside injured reserve, or `None` when the bench is
+    /// unlimited.
+    pub fn roster_size(&self) -> Option<usize> {
+        (!self.is_bench_unlimited).then(|| {
+            self.lineup_slot_counts
+                .iter()
+                .filter(|(slot, _)| !slot.is_injured_reserve())
+                .map(|(_, count)| (*count).max(0) as usize)
+                .sum()
+        })
+    }
+}
+
 #[derive(Deserialize, Debug, Serialize)]
 pub struct ScheduleSettings {
     pub divisions: Vec<Divison>,
@@ -267,6 +304,16 @@ pub struct ScheduleSettings {
     pub playoff_team_count: u8,
 }
 
+impl ScheduleSettings {
+    /// The matchup period a scoring period belongs to.
+    pub fn matchup_period_of(&self, scoring_period_id: u8) -> Option<u8> {
+        self.matchup_periods
+            .iter()
+            .find(|(_, scoring_periods)| scoring_periods.contains(&scoring_period_id))
+            .map(|(matchup_period_id, _)| *matchup_period_id)
+    }
+}
+
 #[derive(Deserialize, Debug, Serialize)]
 pub struct Divison {
     pub id: u32,
@@ -281,23 +328,142 @@ pub struct ScoringSettings {
     #[serde(rename = "homeTeamBonus")]
     pub home_team_bonus: f32,
     #[serde(rename = "matchupTieRule")]
-    pub matchup_tie_rule: String, //TODO: Enum of possible values; Known: NONE
+    pub matchup_tie_rule: MatchupTieRule,
     #[serde(rename = "matchupTieRuleBy")]
     pub matchup_tie_rule_by: i8,
     #[serde(rename = "playerRankType")]
-    pub player_rank_type: String, //TODO: Enum of possible values; Known: PPR
+    pub player_rank_type: PlayerRankType,
     #[serde(rename = "playoffHomeTeamBonus")]
     pub playoff_home_team_bonus: f32,
     #[serde(rename = "playoffMatchupTieRule")]
-    pub playoff_matchup_tie_rule: String, //TODO: Enum of possible values; Known: NONE
+    pub playoff_matchup_tie_rule: MatchupTieRule,
     #[serde(rename = "playoffMatchupTieRuleBy")]
     pub playoff_matchup_tie_rule_by: i8,
     #[serde(rename = "scoringEnhancementType")]
-    pub scoring_enhancement_type: String, //TODO: Enum of possible values; Known: WIN_BONUS_TOP_HALF
+    pub scoring_enhancement_type: ScoringEnhancementType,
     #[serde(rename = "scoringItems")]
     pub scoring_items: Vec<ScoringItems>,
     #[serde(rename = "scoringType")]
-    pub scoring_type: String, //TODO: Enum of possible values; Known: H2H_POINTS
+    pub scoring_type: ScoringType,
+}
+
+impl ScoringSettings {
+    /// The tie rule in effect for a regular season or playoff matchup.
+    pub fn tie_rule(&self, playoff: bool) -> &MatchupTieRule {
+        if playoff {
+            &self.playoff_matchup_tie_rule
+        } else {
+            &self.matchup_tie_rule
+        }
+    }
+}
+
+string_enum! {
+    /// How a matchup that ends with equal scores is decided.
+    pub enum MatchupTieRule {
+        /// The matchup stays a tie.
+        None => "NONE",
+        /// The team with more points scored on the season wins.
+        TotalPointsScored => "TOTAL_POINTS_SCORED",
+        /// The team whose bench scored more wins.
+        MostBenchPoints => "MOST_BENCH_POINTS",
+        HomeTeamWins => "HOME_TEAM_WINS",
+    }
+}
+
+impl MatchupTieRule {
+    /// The winning team of a matchup, applying the tie rule when the scores are level.
+    ///
+    /// `home_tiebreaker` and `away_tiebreaker` are each team's value for whatever the rule is
+    /// decided by, such as season points or bench points. Returns `None` for a bye, or when the
+    /// matchup remains tied.
+    pub fn resolve(
+        &self,
+        matchup: &Matchup,
+        home_tiebreaker: f32,
+        away_tiebreaker: f32,
+    ) -> Option<TeamId> {
+        let home = matchup.home.as_ref()?;
+        let away = matchup.away.as_ref()?;
+        if home.total_points > away.total_points {
+            return Some(home.team_id);
+        }
+        if away.total_points > home.total_points {
+            return Some(away.team_id);
+        }
+        match self {
+            MatchupTieRule::None | MatchupTieRule::Other(_) => None,
+            MatchupTieRule::HomeTeamWins => Some(home.team_id),
+            MatchupTieRule::TotalPointsScored | MatchupTieRule::MostBenchPoints => {
+                if home_tiebreaker > away_tiebreaker {
+                    Some(home.team_id)
+                } else if away_tiebreaker > home_tiebreaker {
+                    Some(away.team_id)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+string_enum! {
+    /// Which of ESPN's player rankings the league uses.
+    pub enum PlayerRankType {
+        Standard => "STANDARD",
+        Ppr => "PPR",
+    }
+}
+
+impl PlayerRankType {
+    /// Whether receptions earn points under this ranking.
+    pub fn is_ppr(&self) -> bool {
+        matches!(self, PlayerRankType::Ppr)
+    }
+}
+
+string_enum! {
+    /// Extra results awarded on top of the head to head matchup.
+    pub enum ScoringEnhancementType {
+        None => "NONE",
+        /// Teams scoring in the top half of the league each week earn an additional win.
+        WinBonusTopHalf => "WIN_BONUS_TOP_HALF",
+    }
+}
+
+impl ScoringEnhancementType {
+    pub fn awards_top_half_win(&self) -> bool {
+        matches!(self, ScoringEnhancementType::WinBonusTopHalf)
+    }
+}
+
+string_enum! {
+    pub enum ScoringType {
+        HeadToHeadPoints => "H2H_POINTS",
+        HeadToHeadCategory => "H2H_CATEGORY",
+        HeadToHeadMostCategories => "H2H_MOST_CATEGORIES",
+        Rotisserie => "ROTO",
+        TotalPoints => "POINTS",
+    }
+}
+
+impl ScoringType {
+    /// Whether teams face a single opponent each matchup period.
+    pub fn is_head_to_head(&self) -> bool {
+        matches!(
+            self,
+            ScoringType::HeadToHeadPoints
+                | ScoringType::HeadToHeadCategory
+                | ScoringType::HeadToHeadMostCategories
+        )
+    }
+    /// Whether results are decided by fantasy points rather than statistical categories.
+    pub fn is_points_based(&self) -> bool {
+        matches!(
+            self,
+            ScoringType::HeadToHeadPoints | ScoringType::TotalPoints
+        )
+    }
 }
 
 #[derive(Deserialize, Debug, Serialize)]
@@ -320,10 +486,62 @@ pub struct TradeSettings {
     #[serde(rename = "allowOutOfUniverse")]
     pub allow_out_of_universe: bool,
     #[serde(rename = "deadlineDate")]
-    pub deadline_date: u64,
+    pub deadline_date: Timestamp,
     pub max: i32,
     #[serde(rename = "revisionHours")]
     pub revision_hours: u16,
     #[serde(rename = "vetoVotesRequired")]
     pub veto_votes_required: i8,
 }
+
+impl TradeSettings {
+    /// Whether trades are closed at `now`. Leagues without a trade deadline never are.
+    pub fn is_past_deadline(&self, now: impl Into<Timestamp>) -> bool {
+        self.deadline_date.is_set() && now.into() > self.deadline_date
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::matchup::{test::performance, MatchupOutcome};
+
+    fn matchup(home: f32, away: f32) -> Matchup {
+        Matchup {
+            away: performance(2, away),
+            home: performance(1, home),
+            id: 1,
+            matchup_period_id: 1,
+            playoff_tier_type: None,
+            winner: MatchupOutcome::Undecided,
+        }
+    }
+
+    #[test]
+    fn unknown_rules_fall_back_to_other() {
+        let rule: MatchupTieRule = serde_json::from_str("\"NONE\"").unwrap();
+        assert_eq!(rule, MatchupTieRule::None);
+        let rule: MatchupTieRule = serde_json::from_str("\"COIN_FLIP\"").unwrap();
+        assert_eq!(rule, MatchupTieRule::Other("COIN_FLIP".to_string()));
+        assert_eq!(serde_json::to_string(&rule).unwrap(), "\"COIN_FLIP\"");
+    }
+
+    #[test]
+    fn tie_rules_resolve_tied_matchups() {
+        let tied = matchup(100.0, 100.0);
+        assert_eq!(MatchupTieRule::None.resolve(&tied, 10.0, 5.0), None);
+        assert_eq!(
+            MatchupTieRule::HomeTeamWins.resolve(&tied, 0.0, 5.0),
+            Some(TeamId(1))
+        );
+        assert_eq!(
+            MatchupTieRule::MostBenchPoints.resolve(&tied, 10.0, 25.0),
+            Some(TeamId(2))
+        );
+        let decided = matchup(90.0, 100.0);
+        assert_eq!(
+            MatchupTieRule::HomeTeamWins.resolve(&decided, 0.0, 0.0),
+            Some(TeamId(2))
+        );
+    }
+}
@@ -1,9 +1,36 @@
+#[macro_use]
+mod macros;
+
+pub mod box_score;
+pub mod calendar;
 pub mod client;
+pub mod crawler;
+pub mod fixture;
 pub mod free_agent;
+pub mod history;
 pub mod id_maps;
 pub mod league;
+pub mod live;
+pub mod lineup;
 pub mod matchup;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod members;
 pub mod player;
+pub mod position;
+pub mod pro_team;
+pub mod projection_accuracy;
+pub mod raw;
+pub mod record_book;
+pub mod registry;
+pub mod schema;
+pub mod simulation;
+pub mod stat_discovery;
+pub mod source;
+pub mod status;
+pub mod synthetic;
 pub mod team;
-pub mod week;
+pub mod timestamp;
+pub mod trade;
+pub mod waiver;
+pub mod win_probability;
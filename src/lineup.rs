@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+
+use super::{league::RosterSettings, player::PlayerId, position::LineupSlot};
+
+/// A player who could be placed in the lineup, along with the points they are expected to score.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct LineupCandidate {
+    pub player_id: PlayerId,
+    pub eligible_slots: Vec<LineupSlot>,
+    pub points: f32,
+}
+
+/// A set of starters, one per lineup slot.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Lineup {
+    pub starters: Vec<(LineupSlot, LineupCandidate)>,
+    pub points: f32,
+}
+
+/// Pick the highest scoring set of starters the league's roster settings allow.
+///
+/// Players are assigned to slots to maximize total points, filling as many slots as possible
+/// first, so a player eligible for several slots goes wherever the lineup gains the most.
+pub fn optimal_lineup(settings: &RosterSettings, candidates: &[LineupCandidate]) -> Lineup {
+    let mut slots = settings
+        .lineup_slot_counts
+        .iter()
+        .filter(|(slot, count)| slot.is_starter() && **count > 0)
+        .flat_map(|(slot, count)| std::iter::repeat_n(*slot, *count as usize))
+        .collect::<Vec<_>>();
+    slots.sort_by_key(|slot| slot.id());
+
+    // Each filled slot is worth far more than any projection, so an empty slot never wins out.
+    const FILLED: f64 = 1e6;
+    let columns = candidates.len().max(slots.len());
+    let costs = slots
+        .iter()
+        .map(|slot| {
+            (0..columns)
+                .map(|c| match candidates.get(c) {
+                    Some(player) if player.eligible_slots.contains(slot) => {
+                        -(FILLED + player.points as f64)
+                    }
+                    _ => 0.0,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut lineup = Lineup::default();
+    for (slot, column) in slots.iter().zip(assign(&costs)) {
+        match candidates.get(column) {
+            Some(player) if player.eligible_slots.contains(slot) => {
+                lineup.points += player.points;
+                lineup.starters.push((*slot, player.clone()));
+            }
+            _ => {}
+        }
+    }
+    lineup
+}
+
+/// The column assigned to each row that minimizes total cost, by the Hungarian algorithm.
+/// There must be at least as many columns as rows.
+fn assign(costs: &[Vec<f64>]) -> Vec<usize> {
+    let rows = costs.len();
+    let columns = costs.first().map_or(0, |r| r.len());
+    // Potentials and matches are 1-indexed, with index 0 as the unmatched sentinel.
+    let mut row_potential = vec![0.0; rows + 1];
+    let mut column_potential = vec![0.0; columns + 1];
+    let mut matched_row = vec![0; columns + 1];
+    let mut previous = vec![0; columns + 1];
+    for row in 1..=rows {
+        matched_row[0] = row;
+        let mut column = 0;
+        let mut slack = vec![f64::INFINITY; columns + 1];
+        let mut used = vec![false; columns + 1];
+        loop {
+            used[column] = true;
+            let current = matched_row[column];
+            let mut delta = f64::INFINITY;
+            let mut next = 0;
+            for c in 1..=columns {
+                if used[c] {
+                    continue;
+                }
+                let reduced =
+                    costs[current - 1][c - 1] - row_potential[current] - column_potential[c];
+                if reduced < slack[c] {
+                    slack[c] = reduced;
+                    previous[c] = column;
+                }
+                if slack[c] < delta {
+                    delta = slack[c];
+                    next = c;
+                }
+            }
+            for c in 0..=columns {
+                if used[c] {
+                    row_potential[matched_row[c]] += delta;
+                    column_potential[c] -= delta;
+                } else {
+                    slack[c] -= delta;
+                }
+            }
+            column = next;
+            if matched_row[column] == 0 {
+                break;
+            }
+        }
+        while column != 0 {
+            let before = previous[column];
+            matched_row[column] = matched_row[before];
+            column = before;
+        }
+    }
+    let mut assignment = vec![0; rows];
+    for c in 1..=columns {
+        if matched_row[c] != 0 {
+            assignment[matched_row[c] - 1] = c - 1;
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn settings(slots: &[(u64, i8)]) -> RosterSettings {
+        RosterSettings {
+            is_bench_unlimited: false,
+            is_using_undroppable_list: false,
+            lineup_locktime_type: "INDIVIDUAL_GAME".to_string(),
+            lineup_slot_counts: slots
+                .iter()
+                .map(|(slot, count)| (LineupSlot::from(*slot), *count))
+                .collect(),
+            lineup_slot_stat_limits: HashMap::new(),
+            move_limit: -1,
+            position_limits: HashMap::new(),
+            roster_locktime_type: "INDIVIDUAL_GAME".to_string(),
+            universe_ids: vec![1],
+        }
+    }
+
+    fn candidate(id: i64, slots: &[u64], points: f32) -> LineupCandidate {
+        LineupCandidate {
+            player_id: PlayerId(id),
+            eligible_slots: slots.iter().map(|s| LineupSlot::from(*s)).collect(),
+            points,
+        }
+    }
+
+    #[test]
+    fn fills_dedicated_slots_before_flex() {
+        // One RB, one WR, one RB/WR/TE flex and a bench.
+        let settings = settings(&[(2, 1), (4, 1), (23, 1), (20, 7)]);
+        let candidates = vec![
+            candidate(1, &[2, 23, 20], 20.0),
+            candidate(2, &[2, 23, 20], 15.0),
+            candidate(3, &[4, 23, 20], 10.0),
+            candidate(4, &[4, 23, 20], 5.0),
+        ];
+        let lineup = optimal_lineup(&settings, &candidates);
+        assert_eq!(lineup.starters.len(), 3);
+        assert_eq!(lineup.points, 45.0);
+        let wr = lineup
+            .starters
+            .iter()
+            .find(|(slot, _)| *slot == LineupSlot::WideReceiver)
+            .unwrap();
+        assert_eq!(wr.1.player_id, PlayerId(3));
+    }
+
+    #[test]
+    fn leaves_slots_empty_without_eligible_players() {
+        let settings = settings(&[(0, 1), (17, 1)]);
+        let candidates = vec![candidate(1, &[0, 7, 20], 18.5)];
+        let lineup = optimal_lineup(&settings, &candidates);
+        assert_eq!(lineup.starters.len(), 1);
+        assert_eq!(lineup.points, 18.5);
+    }
+
+    #[test]
+    fn assigns_multi_position_players_where_they_gain_most() {
+        // One RB/WR flex and one WR/TE flex; greedy filling would put the WR in the first.
+        let settings = settings(&[(3, 1), (5, 1)]);
+        let candidates = vec![
+            candidate(1, &[2, 3], 5.0),
+            candidate(2, &[4, 3, 5], 10.0),
+            candidate(3, &[6, 5], 1.0),
+        ];
+        let lineup = optimal_lineup(&settings, &candidates);
+        assert_eq!(lineup.points, 15.0);
+        assert!(lineup
+            .starters
+            .contains(&(LineupSlot::WideReceiverTightEnd, candidates[1].clone())));
+    }
+
+    #[test]
+    fn fills_every_slot_it_can_before_maximizing_points() {
+        let settings = settings(&[(2, 1), (23, 1)]);
+        let candidates = vec![candidate(1, &[2, 23], 30.0), candidate(2, &[2], -1.0)];
+        let lineup = optimal_lineup(&settings, &candidates);
+        assert_eq!(lineup.starters.len(), 2);
+        assert_eq!(lineup.points, 29.0);
+    }
+}
@@ -0,0 +1,672 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Duration,
+};
+
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    calendar::WeekRef,
+    client::EspnClient,
+    id_maps::ProTeamId,
+    matchup::{Matchup, MatchupOutcome, RosterSlot, TeamMatchupPerformance},
+    player::{PlayerId, StatSource, Stats},
+    position::LineupSlot,
+    pro_team::{games_for_week, GameStatus, ProGame},
+    source::FetchError,
+    team::TeamId,
+    timestamp::Timestamp,
+};
+
+/// Something that changed between two polls of a week's matchups.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum ScoreUpdate {
+    PlayerScored {
+        matchup_id: u16,
+        team: TeamId,
+        player_id: PlayerId,
+        player_name: String,
+        /// Points gained since the last poll. Negative for stat corrections or fumbles.
+        points: f32,
+        total: f32,
+    },
+    LeadChange {
+        matchup_id: u16,
+        leader: TeamId,
+        trailer: TeamId,
+        margin: f32,
+    },
+    MatchupFinal {
+        matchup_id: u16,
+        /// `None` for a tie.
+        winner: Option<TeamId>,
+        home_points: f32,
+        away_points: f32,
+    },
+}
+
+/// A starter or bench player's scoring so far in the week.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct LivePlayer {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub pro_team: ProTeamId,
+    pub slot: LineupSlot,
+    pub points: f32,
+    /// The player's projected stat line for the scoring period.
+    pub projection: Option<Stats>,
+    /// Projected points for each of the matchup period's scoring periods after this one, from the
+    /// player's projection for the period or their season average.
+    pub later_projections: Vec<f32>,
+    pub game: GameStatus,
+}
+
+impl LivePlayer {
+    fn from_roster_slot(
+        entry: &RosterSlot,
+        season: u16,
+        scoring_period_id: u8,
+        last_scoring_period_id: u8,
+        games: &HashMap<ProTeamId, ProGame>,
+        now: Timestamp,
+    ) -> LivePlayer {
+        let player = &entry.player_pool_entry.player;
+        LivePlayer {
+            player_id: player.id,
+            name: player.full_name.clone(),
+            pro_team: player.pro_team_id.clone(),
+            slot: entry.lineup_slot_id,
+            points: player
+                .actual_points(season, scoring_period_id)
+                .unwrap_or_default(),
+            projection: player
+                .stat_line(StatSource::Projected, season, Some(scoring_period_id))
+                .cloned(),
+            later_projections: (scoring_period_id + 1..=last_scoring_period_id)
+                .map(|sp| {
+                    player
+                        .projected_points(season, sp)
+                        .or_else(|| player.projected_average(season))
+                        .unwrap_or_default()
+                })
+                .collect(),
+            game: games
+                .get(&player.pro_team_id)
+                .map(|g| g.status(now))
+                .unwrap_or(GameStatus::Bye),
+        }
+    }
+
+    pub fn projected_points(&self) -> Option<f32> {
+        self.projection.as_ref().map(|p| p.applied_total)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct LiveTeam {
+    pub team: TeamId,
+    pub points: f32,
+    pub players: Vec<LivePlayer>,
+}
+
+impl LiveTeam {
+    fn from_performance(
+        performance: &TeamMatchupPerformance,
+        season: u16,
+        scoring_period_id: u8,
+        last_scoring_period_id: u8,
+        games: &HashMap<ProTeamId, ProGame>,
+        now: Timestamp,
+    ) -> LiveTeam {
+        let players = performance
+            .roster_for_current_scoring_period
+            .iter()
+            .flat_map(|r| r.entries.iter())
+            .map(|e| {
+                LivePlayer::from_roster_slot(
+                    e,
+                    season,
+                    scoring_period_id,
+                    last_scoring_period_id,
+                    games,
+                    now,
+                )
+            })
+            .collect::<Vec<_>>();
+        LiveTeam {
+            team: performance.team_id,
+            points: performance.total_points_live.unwrap_or_else(|| {
+                players
+                    .iter()
+                    .filter(|p| p.slot.is_starter())
+                    .map(|p| p.points)
+                    .sum()
+            }),
+            players,
+        }
+    }
+
+    pub fn starters(&self) -> impl Iterator<Item = &LivePlayer> {
+        self.players.iter().filter(|p| p.slot.is_starter())
+    }
+
+    /// Starters whose games haven't kicked off.
+    pub fn yet_to_play(&self) -> impl Iterator<Item = &LivePlayer> {
+        self.starters().filter(|p| p.game == GameStatus::NotStarted)
+    }
+
+    /// Starters who can still score, including those playing now.
+    pub fn remaining(&self) -> impl Iterator<Item = &LivePlayer> {
+        self.starters().filter(|p| p.game.is_remaining())
+    }
+
+    /// Whether the team has a roster and every starter's stats are official. A team without a
+    /// roster is never settled, since there's no telling who is still to play.
+    pub fn is_settled(&self) -> bool {
+        !self.players.is_empty() && self.starters().all(|p| p.game.is_settled())
+    }
+}
+
+/// A matchup as it stands mid-week.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct LiveMatchup {
+    pub matchup_id: u16,
+    pub scoring_period_id: u8,
+    /// The last scoring period of the matchup period. Matchups spanning several scoring periods
+    /// can only finish in this one.
+    pub last_scoring_period_id: u8,
+    pub winner: MatchupOutcome,
+    pub home: LiveTeam,
+    /// `None` for a bye.
+    pub away: Option<LiveTeam>,
+}
+
+impl LiveMatchup {
+    /// # Arguments
+    ///
+    /// * last_scoring_period_id - The last scoring period of the matchup's matchup period; see
+    ///   `SeasonCalendar::scoring_periods`.
+    pub fn from_matchup(
+        matchup: &Matchup,
+        season: u16,
+        scoring_period_id: u8,
+        last_scoring_period_id: u8,
+        games: &HashMap<ProTeamId, ProGame>,
+        now: Timestamp,
+    ) -> Option<LiveMatchup> {
+        let team = |p: &TeamMatchupPerformance| {
+            LiveTeam::from_performance(
+                p,
+                season,
+                scoring_period_id,
+                last_scoring_period_id,
+                games,
+                now,
+            )
+        };
+        Some(LiveMatchup {
+            matchup_id: matchup.id,
+            scoring_period_id,
+            last_scoring_period_id,
+            winner: matchup.winner.clone(),
+            home: team(matchup.home.as_ref()?),
+            away: matchup.away.as_ref().map(team),
+        })
+    }
+
+    pub fn teams(&self) -> impl Iterator<Item = &LiveTeam> {
+        std::iter::once(&self.home).chain(self.away.as_ref())
+    }
+
+    /// The team ahead, or `None` when level or on a bye.
+    pub fn leader(&self) -> Option<TeamId> {
+        let away = self.away.as_ref()?;
+        if self.home.points > away.points {
+            Some(self.home.team)
+        } else if away.points > self.home.points {
+            Some(away.team)
+        } else {
+            None
+        }
+    }
+
+    /// Whether ESPN has decided the matchup, or it's the matchup period's last scoring period and
+    /// every starter's stats are official.
+    pub fn is_final(&self) -> bool {
+        self.winner.is_decided()
+            || (self.scoring_period_id >= self.last_scoring_period_id
+                && self.teams().all(|t| t.is_settled()))
+    }
+}
+
+/// The updates between two polls of the same matchup.
+pub fn diff(previous: &LiveMatchup, current: &LiveMatchup) -> Vec<ScoreUpdate> {
+    let mut updates = Vec::new();
+    for (before, after) in previous.teams().zip(current.teams()) {
+        let points = before
+            .players
+            .iter()
+            .map(|p| (p.player_id, p.points))
+            .collect::<HashMap<_, _>>();
+        for player in &after.players {
+            let change = player.points - points.get(&player.player_id).copied().unwrap_or(0.0);
+            if change.abs() > f32::EPSILON {
+                updates.push(ScoreUpdate::PlayerScored {
+                    matchup_id: current.matchup_id,
+                    team: after.team,
+                    player_id: player.player_id,
+                    player_name: player.name.clone(),
+                    points: change,
+                    total: player.points,
+                });
+            }
+        }
+    }
+    if let (Some(leader), Some(away)) = (current.leader(), current.away.as_ref()) {
+        if previous.leader() != Some(leader) {
+            let trailer = if leader == current.home.team {
+                away.team
+            } else {
+                current.home.team
+            };
+            updates.push(ScoreUpdate::LeadChange {
+                matchup_id: current.matchup_id,
+                leader,
+                trailer,
+                margin: (current.home.points - away.points).abs(),
+            });
+        }
+    }
+    if current.is_final() && !previous.is_final() {
+        updates.extend(final_update(current));
+    }
+    updates
+}
+
+/// The result of a final matchup, or `None` for a bye. The winner is ESPN's when it has decided
+/// the matchup, which can differ from the leader after a tiebreak, and the leader otherwise.
+fn final_update(matchup: &LiveMatchup) -> Option<ScoreUpdate> {
+    let away = matchup.away.as_ref()?;
+    let winner = match matchup.winner {
+        MatchupOutcome::Home => Some(matchup.home.team),
+        MatchupOutcome::Away => Some(away.team),
+        MatchupOutcome::Tie => None,
+        _ => matchup.leader(),
+    };
+    Some(ScoreUpdate::MatchupFinal {
+        matchup_id: matchup.matchup_id,
+        winner,
+        home_points: matchup.home.points,
+        away_points: away.points,
+    })
+}
+
+/// Polls a week's matchups and reports what changed.
+///
+/// ```no_run
+/// # async fn run(client: espn_fantasy_football::client::EspnClient) {
+/// use espn_fantasy_football::live::LiveTracker;
+/// use futures::StreamExt;
+///
+/// let mut updates = Box::pin(LiveTracker::new(&client, 2023, 5).updates());
+/// while let Some(update) = updates.next().await {
+///     match update {
+///         Ok(update) => println!("{:?}", update),
+///         Err(e) => eprintln!("Stopped tracking: {}", e),
+///     }
+/// }
+/// # }
+/// ```
+pub struct LiveTracker<'a> {
+    client: &'a EspnClient,
+    season: u16,
+    week: WeekRef,
+    /// How long to wait between polls.
+    pub interval: Duration,
+    /// How many polls in a row that fail with a transient error `updates` retries before giving
+    /// up.
+    pub retries: u32,
+    games: Option<HashMap<ProTeamId, ProGame>>,
+    /// Each matchup period's scoring periods, from the league's schedule settings.
+    matchup_periods: HashMap<u8, Vec<u8>>,
+    matchups: HashMap<u16, LiveMatchup>,
+    finished: HashSet<u16>,
+    polled: bool,
+}
+
+impl<'a> LiveTracker<'a> {
+    pub fn new(client: &'a EspnClient, season: u16, week: impl Into<WeekRef>) -> LiveTracker<'a> {
+        LiveTracker {
+            client,
+            season,
+            week: week.into(),
+            interval: Duration::from_secs(60),
+            retries: 3,
+            games: None,
+            matchup_periods: HashMap::new(),
+            matchups: HashMap::new(),
+            finished: HashSet::new(),
+            polled: false,
+        }
+    }
+
+    /// The matchups as of the last poll.
+    pub fn matchups(&self) -> impl Iterator<Item = &LiveMatchup> {
+        self.matchups.values()
+    }
+
+    /// Whether every matchup has finished, or is done with this scoring period when it goes on
+    /// into the next. A week without matchups is finished once it has been polled.
+    pub fn is_finished(&self) -> bool {
+        self.polled && self.finished.len() == self.matchups.len()
+    }
+
+    /// Fetches the matchups once. The first poll only records where things stand, apart from
+    /// matchups that are already final.
+    pub async fn poll(&mut self) -> Result<Vec<ScoreUpdate>, FetchError> {
+        if self.games.is_none() {
+            let pro_teams = self.client.try_get_pro_team_schedules(self.season).await?;
+            let settings = self.client.try_get_league_settings(self.season).await?;
+            self.matchup_periods = settings.schedule_settings.matchup_periods;
+            self.games = Some(games_for_week(&pro_teams, self.week.scoring_period_id()));
+        }
+        let games = self.games.as_ref().expect("schedule loaded above");
+        let now = Timestamp::now();
+        let mut updates = Vec::new();
+        let scoring_period_id = self.week.scoring_period_id();
+        let matchups = self
+            .client
+            .try_get_live_matchups(self.season, self.week)
+            .await?;
+        for matchup in matchups {
+            let last_scoring_period_id = self
+                .matchup_periods
+                .get(&matchup.matchup_period_id)
+                .and_then(|periods| periods.iter().max().copied())
+                .unwrap_or(scoring_period_id);
+            let current = match LiveMatchup::from_matchup(
+                &matchup,
+                self.season,
+                scoring_period_id,
+                last_scoring_period_id,
+                games,
+                now,
+            ) {
+                Some(m) => m,
+                None => continue,
+            };
+            match self.matchups.get(&current.matchup_id) {
+                Some(previous) => updates.append(&mut diff(previous, &current)),
+                None if current.is_final() => updates.extend(final_update(&current)),
+                None => {}
+            }
+            if current.is_final() || current.teams().all(|t| t.is_settled()) {
+                self.finished.insert(current.matchup_id);
+            }
+            self.matchups.insert(current.matchup_id, current);
+        }
+        self.polled = true;
+        Ok(updates)
+    }
+
+    /// Polls every `interval` until all matchups are final, yielding updates as they happen.
+    ///
+    /// A poll that fails with a transient error is tried again at the next interval, up to
+    /// `retries` times in a row. Any other failure is yielded and ends the stream.
+    pub fn updates(self) -> impl Stream<Item = Result<ScoreUpdate, FetchError>> + 'a {
+        futures::stream::unfold(
+            (Some(self), VecDeque::new(), true),
+            |(tracker, mut pending, mut first)| async move {
+                let mut tracker = tracker?;
+                let mut failures = 0;
+                loop {
+                    if let Some(update) = pending.pop_front() {
+                        return Some((Ok(update), (Some(tracker), pending, first)));
+                    }
+                    if !first {
+                        if tracker.is_finished() {
+                            return None;
+                        }
+                        tokio::time::sleep(tracker.interval).await;
+                    }
+                    first = false;
+                    match tracker.poll().await {
+                        Ok(updates) => {
+                            failures = 0;
+                            pending.extend(updates);
+                        }
+                        Err(e) if e.is_transient() && failures < tracker.retries => failures += 1,
+                        Err(e) => return Some((Err(e), (None, pending, first))),
+                    }
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::Path,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+    };
+
+    use async_trait::async_trait;
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::source::{DataSource, EspnRequest, FileSource};
+
+    fn player(id: i64, points: f32) -> LivePlayer {
+        LivePlayer {
+            player_id: PlayerId(id),
+            name: format!("Player {}", id),
+            pro_team: ProTeamId(1),
+            slot: LineupSlot::Quarterback,
+            points,
+            projection: None,
+            later_projections: vec![],
+            game: GameStatus::InProgress,
+        }
+    }
+
+    fn matchup(home: f32, away: f32) -> LiveMatchup {
+        LiveMatchup {
+            matchup_id: 1,
+            scoring_period_id: 1,
+            last_scoring_period_id: 1,
+            winner: MatchupOutcome::Undecided,
+            home: LiveTeam {
+                team: TeamId(1),
+                points: home,
+                players: vec![player(10, home)],
+            },
+            away: Some(LiveTeam {
+                team: TeamId(2),
+                points: away,
+                players: vec![player(20, away)],
+            }),
+        }
+    }
+
+    #[test]
+    fn reports_scoring_and_lead_changes() {
+        let updates = diff(&matchup(10.0, 8.0), &matchup(10.0, 14.0));
+        assert_eq!(
+            updates,
+            vec![
+                ScoreUpdate::PlayerScored {
+                    matchup_id: 1,
+                    team: TeamId(2),
+                    player_id: PlayerId(20),
+                    player_name: "Player 20".to_string(),
+                    points: 6.0,
+                    total: 14.0,
+                },
+                ScoreUpdate::LeadChange {
+                    matchup_id: 1,
+                    leader: TeamId(2),
+                    trailer: TeamId(1),
+                    margin: 4.0,
+                },
+            ]
+        );
+
+        let mut finished = matchup(10.0, 14.0);
+        finished.winner = MatchupOutcome::Away;
+        assert_eq!(
+            diff(&matchup(10.0, 14.0), &finished),
+            vec![ScoreUpdate::MatchupFinal {
+                matchup_id: 1,
+                winner: Some(TeamId(2)),
+                home_points: 10.0,
+                away_points: 14.0,
+            }]
+        );
+    }
+
+    fn settled(mut matchup: LiveMatchup, game: GameStatus) -> LiveMatchup {
+        for team in [Some(&mut matchup.home), matchup.away.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            team.players.iter_mut().for_each(|p| p.game = game);
+        }
+        matchup
+    }
+
+    #[test]
+    fn finishes_once_official_stats_are_in() {
+        assert!(!matchup(10.0, 14.0).is_final());
+        assert!(!settled(matchup(10.0, 14.0), GameStatus::Unofficial).is_final());
+        let finished = settled(matchup(10.0, 14.0), GameStatus::Final);
+        assert!(finished.is_final());
+        assert_eq!(
+            diff(&matchup(10.0, 14.0), &finished),
+            vec![ScoreUpdate::MatchupFinal {
+                matchup_id: 1,
+                winner: Some(TeamId(2)),
+                home_points: 10.0,
+                away_points: 14.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn multi_week_matchups_finish_in_their_last_scoring_period() {
+        let mut first_week = settled(matchup(10.0, 14.0), GameStatus::Final);
+        first_week.last_scoring_period_id = 2;
+        assert!(!first_week.is_final());
+        assert!(diff(&matchup(10.0, 14.0), &first_week).is_empty());
+
+        let mut second_week = first_week.clone();
+        second_week.scoring_period_id = 2;
+        assert!(second_week.is_final());
+    }
+
+    #[test]
+    fn missing_rosters_are_not_final() {
+        let mut matchup = settled(matchup(10.0, 14.0), GameStatus::Final);
+        matchup.away.as_mut().unwrap().players.clear();
+        assert!(!matchup.is_final());
+
+        matchup.away = None;
+        assert!(matchup.is_final());
+    }
+
+    #[test]
+    fn final_updates_follow_espn_and_skip_byes() {
+        let mut tiebreak = settled(matchup(14.0, 14.0), GameStatus::Final);
+        tiebreak.winner = MatchupOutcome::Home;
+        assert_eq!(
+            diff(&matchup(14.0, 14.0), &tiebreak),
+            vec![ScoreUpdate::MatchupFinal {
+                matchup_id: 1,
+                winner: Some(TeamId(1)),
+                home_points: 14.0,
+                away_points: 14.0,
+            }]
+        );
+
+        let mut bye = settled(matchup(10.0, 14.0), GameStatus::Final);
+        bye.away = None;
+        let mut before = matchup(10.0, 14.0);
+        before.away = None;
+        assert!(bye.is_final());
+        assert!(diff(&before, &bye).is_empty());
+    }
+
+    /// Serves the 2023 fixtures, answering live scoring requests with week 1's matchups. Live
+    /// scoring fails with a 503 `unavailable` times, and with `error` every time when set.
+    #[derive(Default)]
+    struct Live {
+        unavailable: AtomicU32,
+        error: Option<FetchError>,
+    }
+
+    #[async_trait]
+    impl DataSource for Live {
+        async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+            let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/espn/ppr");
+            if !request.views().contains(&"mLiveScoring") {
+                return FileSource::new(fixtures).fetch(request).await;
+            }
+            if let Some(e) = &self.error {
+                return Err(e.clone());
+            }
+            let unavailable = self.unavailable.load(Ordering::SeqCst);
+            if unavailable > 0 {
+                self.unavailable.store(unavailable - 1, Ordering::SeqCst);
+                return Err(FetchError::Status(503));
+            }
+            Ok(
+                std::fs::read_to_string(fixtures.join("2023/mMatchup+mMatchupScore_1.json"))
+                    .unwrap(),
+            )
+        }
+    }
+
+    async fn track(source: Live, week: u8) -> Vec<Result<ScoreUpdate, FetchError>> {
+        let client = EspnClient::with_source(1, Arc::new(source));
+        let mut tracker = LiveTracker::new(&client, 2023, week);
+        tracker.interval = Duration::ZERO;
+        tracker.updates().collect().await
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures() {
+        let source = Live {
+            unavailable: AtomicU32::new(3),
+            ..Live::default()
+        };
+        let updates = track(source, 1).await;
+        assert_eq!(updates.len(), 2);
+        assert!(updates
+            .iter()
+            .all(|u| matches!(u, Ok(ScoreUpdate::MatchupFinal { .. }))));
+
+        let source = Live {
+            unavailable: AtomicU32::new(4),
+            ..Live::default()
+        };
+        assert_eq!(track(source, 1).await, vec![Err(FetchError::Status(503))]);
+    }
+
+    #[tokio::test]
+    async fn other_failures_end_the_updates() {
+        let source = Live {
+            error: Some(FetchError::Unauthorized),
+            ..Live::default()
+        };
+        assert_eq!(track(source, 1).await, vec![Err(FetchError::Unauthorized)]);
+    }
+
+    #[tokio::test]
+    async fn weeks_without_matchups_finish() {
+        assert!(track(Live::default(), 30).await.is_empty());
+    }
+}
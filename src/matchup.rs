@@ -2,10 +2,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::{
-    id_maps::PositionId,
     league::DraftDetail,
     player::{Player, PlayerId},
+    position::LineupSlot,
+    raw::Raw,
+    status::{AcquisitionType, InjuryStatus, RosterStatus},
     team::TeamId,
+    timestamp::Timestamp,
 };
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -21,6 +24,14 @@ pub struct MatchupResponse {
     pub scoring_period_id: i8,
     pub schedule: Vec<Matchup>,
 }
+
+impl Raw<MatchupResponse> {
+    /// A matchup along with the JSON ESPN sent for it.
+    pub fn matchup(&self, id: u16) -> Option<Raw<Matchup>> {
+        let index = self.schedule.iter().position(|m| m.id == id)?;
+        self.at(&format!("/schedule/{}", index))
+    }
+}
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Matchup {
     pub away: Option<TeamMatchupPerformance>,
@@ -28,7 +39,108 @@ pub struct Matchup {
     pub id: u16,
     #[serde(rename = "matchupPeriodId")]
     pub matchup_period_id: u8,
-    pub winner: String,
+    #[serde(rename = "playoffTierType")]
+    pub playoff_tier_type: Option<PlayoffTierType>,
+    pub winner: MatchupOutcome,
+}
+
+impl Matchup {
+    /// Byes have only a home team.
+    pub fn is_bye(&self) -> bool {
+        self.home.is_none() || self.away.is_none()
+    }
+
+    pub fn kind(&self) -> MatchupKind {
+        if self.is_bye() {
+            return MatchupKind::Bye;
+        }
+        match &self.playoff_tier_type {
+            None | Some(PlayoffTierType::None) => MatchupKind::Regular,
+            Some(PlayoffTierType::WinnersBracket) => MatchupKind::Playoff,
+            Some(_) => MatchupKind::Consolation,
+        }
+    }
+
+    /// The teams playing, home first.
+    pub fn teams(&self) -> impl Iterator<Item = &TeamMatchupPerformance> {
+        [&self.home, &self.away].into_iter().flatten()
+    }
+
+    pub fn winner_team(&self) -> Option<TeamId> {
+        match self.winner {
+            MatchupOutcome::Home => self.home.as_ref().map(|h| h.team_id),
+            MatchupOutcome::Away => self.away.as_ref().map(|a| a.team_id),
+            _ => None,
+        }
+    }
+
+    pub fn loser_team(&self) -> Option<TeamId> {
+        match self.winner {
+            MatchupOutcome::Home => self.away.as_ref().map(|a| a.team_id),
+            MatchupOutcome::Away => self.home.as_ref().map(|h| h.team_id),
+            _ => None,
+        }
+    }
+
+    /// The points separating the two teams, or `None` for a bye.
+    pub fn margin(&self) -> Option<f32> {
+        Some((self.home.as_ref()?.total_points - self.away.as_ref()?.total_points).abs())
+    }
+
+    pub fn involves(&self, team: TeamId) -> bool {
+        self.side(team).is_some()
+    }
+
+    pub fn side(&self, team: TeamId) -> Option<&TeamMatchupPerformance> {
+        self.teams().find(|t| t.team_id == team)
+    }
+
+    pub fn opponent_of(&self, team: TeamId) -> Option<TeamId> {
+        let home = self.home.as_ref()?;
+        let away = self.away.as_ref()?;
+        if home.team_id == team {
+            Some(away.team_id)
+        } else if away.team_id == team {
+            Some(home.team_id)
+        } else {
+            None
+        }
+    }
+}
+
+string_enum! {
+    /// Which side won a matchup.
+    pub enum MatchupOutcome {
+        Home => "HOME",
+        Away => "AWAY",
+        Tie => "TIE",
+        /// Not yet played, or still in progress.
+        Undecided => "UNDECIDED",
+    }
+}
+
+impl MatchupOutcome {
+    pub fn is_decided(&self) -> bool {
+        !matches!(self, MatchupOutcome::Undecided)
+    }
+}
+
+string_enum! {
+    /// The bracket a playoff matchup belongs to.
+    pub enum PlayoffTierType {
+        None => "NONE",
+        WinnersBracket => "WINNERS_BRACKET",
+        WinnersConsolationLadder => "WINNERS_CONSOLATION_LADDER",
+        LosersConsolationLadder => "LOSERS_CONSOLATION_LADDER",
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MatchupKind {
+    Regular,
+    Playoff,
+    Consolation,
+    Bye,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -45,6 +157,11 @@ pub struct TeamMatchupPerformance {
     pub team_id: TeamId,
     #[serde(rename = "totalPoints")]
     pub total_points: f32,
+    /// Only present when requested with the `mLiveScoring` view.
+    #[serde(rename = "totalPointsLive")]
+    pub total_points_live: Option<f32>,
+    #[serde(rename = "totalProjectedPointsLive")]
+    pub total_projected_points_live: Option<f32>,
 }
 #[derive(Debug, Deserialize, Serialize, Copy, Clone)]
 pub struct CumulativeScore {
@@ -71,20 +188,20 @@ pub struct Roster {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RosterSlot {
     #[serde(rename = "acquisitionDate")]
-    pub acquisition_date: Option<u64>,
+    pub acquisition_date: Option<Timestamp>,
     #[serde(rename = "acquisitionType")]
-    pub acquisition_type: Option<String>,
+    pub acquisition_type: Option<AcquisitionType>,
     #[serde(rename = "injuryStatus")]
-    pub injury_status: Option<String>,
+    pub injury_status: Option<InjuryStatus>,
     #[serde(rename = "lineupSlotId")]
-    pub lineup_slot_id: PositionId,
+    pub lineup_slot_id: LineupSlot,
     #[serde(rename = "pendingTransactionIds")]
     pub pending_transaction_ids: Option<Vec<u64>>,
     #[serde(rename = "playerId")]
     pub player_id: PlayerId,
     #[serde(rename = "playerPoolEntry")]
     pub player_pool_entry: PlayerPoolEntry,
-    pub status: String,
+    pub status: RosterStatus,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -103,7 +220,56 @@ pub struct PlayerPoolEntry {
     pub player: Player,
     #[serde(rename = "rosterLocked")]
     pub roster_locked: bool,
-    pub status: String,
+    pub status: RosterStatus,
     #[serde(rename = "tradeLocked")]
     pub trade_locked: bool,
 }
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    /// A team's side of a matchup with only its total points.
+    pub(crate) fn performance(team: u8, points: f32) -> Option<TeamMatchupPerformance> {
+        Some(TeamMatchupPerformance {
+            cumulative_score: None,
+            games_played: 0,
+            points_by_scoring_period: None,
+            roster_for_current_scoring_period: None,
+            team_id: TeamId(team),
+            total_points: points,
+            total_points_live: None,
+            total_projected_points_live: None,
+        })
+    }
+
+    #[test]
+    fn matchup_helpers() {
+        let matchup: Matchup = serde_json::from_str(
+            r#"{"id": 1, "matchupPeriodId": 15, "winner": "AWAY",
+                "playoffTierType": "WINNERS_BRACKET"}"#,
+        )
+        .unwrap();
+        let matchup = Matchup {
+            home: performance(1, 98.5),
+            away: performance(2, 110.0),
+            ..matchup
+        };
+        assert_eq!(matchup.kind(), MatchupKind::Playoff);
+        assert_eq!(matchup.winner_team(), Some(TeamId(2)));
+        assert_eq!(matchup.loser_team(), Some(TeamId(1)));
+        assert_eq!(matchup.margin(), Some(11.5));
+        assert!(matchup.involves(TeamId(1)));
+        assert_eq!(matchup.opponent_of(TeamId(2)), Some(TeamId(1)));
+        assert_eq!(matchup.opponent_of(TeamId(3)), None);
+
+        let bye = Matchup {
+            away: None,
+            winner: MatchupOutcome::Undecided,
+            ..matchup
+        };
+        assert_eq!(bye.kind(), MatchupKind::Bye);
+        assert_eq!(bye.winner_team(), None);
+        assert_eq!(bye.margin(), None);
+    }
+}
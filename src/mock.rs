@@ -0,0 +1,506 @@
+//! A stand-in for ESPN's fantasy football API, serving leagues from JSON fixtures.
+//!
+//! Fixtures are laid out as:
+//!
+//! - `{season}/{league_id}/league.json`: every field ESPN returns for the league, as if all views
+//!   were requested at once. Free agents go in its `players` array.
+//! - `{season}/{league_id}/league_{scoringPeriodId}.json`: optional. Served instead of
+//!   `league.json` when that scoring period is requested, for rosters and scores that change week
+//!   to week.
+//! - `{season}/{league_id}/auth.json`: optional. Makes the league private; requests must send
+//!   the `SWID` and `espn_s2` cookies it holds, as `{"swid": "..", "espn_s2": ".."}`.
+//! - `{season}/pro_team_schedules.json`: the response to the `proTeamSchedules_wl` view.
+//!
+//! Point a client at it with `HttpSource::with_base_url(&MockServer::base_url(addr), ..)`.
+
+use std::{
+    collections::HashSet,
+    convert::Infallible,
+    net::{SocketAddr, TcpListener},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use hyper::{
+    header::COOKIE,
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// The path ESPN serves the fantasy football API under.
+pub const API_PATH: &str = "/apis/v3/games/ffl";
+
+/// Top level fields returned whatever views are requested.
+const ALWAYS_INCLUDED: [&str; 5] = ["id", "gameId", "seasonId", "segmentId", "scoringPeriodId"];
+
+/// The top level fields each view adds to a league response.
+fn view_fields(view: &str) -> &'static [&'static str] {
+    match view {
+        "mStatus" => &["status"],
+        "mSettings" => &["settings"],
+        "mTeam" => &["teams", "members"],
+        "mRoster" => &["teams"],
+        "mMatchup" | "mMatchupScore" | "mLiveScoring" | "mBoxscore" | "mScoreboard" => {
+            &["schedule"]
+        }
+        "kona_player_info" => &["players", "positionAgainstOpponent"],
+        "mDraftDetail" => &["draftDetail"],
+        "mTransactions2" => &["transactions"],
+        _ => &[],
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Auth {
+    swid: String,
+    espn_s2: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MockServer {
+    pub fixtures: PathBuf,
+}
+
+impl MockServer {
+    pub fn new(fixtures: impl Into<PathBuf>) -> MockServer {
+        MockServer {
+            fixtures: fixtures.into(),
+        }
+    }
+
+    /// The base URL to give `HttpSource::with_base_url` for a server listening on `addr`.
+    pub fn base_url(addr: SocketAddr) -> String {
+        format!("http://{}{}", addr, API_PATH)
+    }
+
+    /// Serves requests on `addr` until the task is dropped.
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), hyper::Error> {
+        let listener = TcpListener::bind(addr).expect("Bind mock server");
+        self.serve_listener(listener).await
+    }
+
+    /// Starts serving on a free local port in the background, returning the address it took.
+    pub fn spawn(self) -> SocketAddr {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("Bind mock server");
+        let addr = listener.local_addr().expect("Mock server address");
+        tokio::spawn(self.serve_listener(listener));
+        addr
+    }
+
+    async fn serve_listener(self, listener: TcpListener) -> Result<(), hyper::Error> {
+        let server = Arc::new(self);
+        let make_service = make_service_fn(move |_| {
+            let server = server.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let server = server.clone();
+                    async move { Ok::<_, Infallible>(server.respond(&req).await) }
+                }))
+            }
+        });
+        Server::from_tcp(listener)?.serve(make_service).await
+    }
+
+    async fn respond(&self, req: &Request<Body>) -> Response<Body> {
+        let result = match route(req.uri().path()) {
+            Some(Route::League { season, league_id }) => {
+                self.league(req, &season, &league_id).await
+            }
+            Some(Route::Season { season }) => self.season(req, &season).await,
+            None => Err(StatusCode::NOT_FOUND),
+        };
+        match result {
+            Ok(body) => Response::builder()
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            Err(status) => Response::builder()
+                .status(status)
+                .body(Body::from(
+                    serde_json::json!({ "messages": [status.to_string()] }).to_string(),
+                ))
+                .unwrap(),
+        }
+    }
+
+    async fn league(
+        &self,
+        req: &Request<Body>,
+        season: &str,
+        league_id: &str,
+    ) -> Result<Value, StatusCode> {
+        let dir = self.fixtures.join(season).join(league_id);
+        if !tokio::fs::metadata(dir.join("league.json"))
+            .await
+            .is_ok_and(|m| m.is_file())
+        {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        if let Some(auth) = read_json::<Auth>(&dir.join("auth.json")).await? {
+            if !authorized(req, &auth) {
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+        }
+        let query = Query::parse(req.uri().query());
+        let league = match query.scoring_period_id {
+            Some(sp) => match read_json::<Value>(&dir.join(format!("league_{}.json", sp))).await? {
+                Some(league) => league,
+                None => read_json(&dir.join("league.json"))
+                    .await?
+                    .ok_or(StatusCode::NOT_FOUND)?,
+            },
+            None => read_json(&dir.join("league.json"))
+                .await?
+                .ok_or(StatusCode::NOT_FOUND)?,
+        };
+        let mut response = select_views(league, &query.views);
+        if let Some(sp) = query.scoring_period_id {
+            response["scoringPeriodId"] = Value::from(sp);
+            filter_stats(&mut response, sp);
+        }
+        if let Some(filter) = req
+            .headers()
+            .get("x-fantasy-filter")
+            .and_then(|f| f.to_str().ok())
+        {
+            let filter: Value =
+                serde_json::from_str(filter).map_err(|_| StatusCode::BAD_REQUEST)?;
+            apply_filter(&mut response, &filter);
+        }
+        Ok(response)
+    }
+
+    async fn season(&self, req: &Request<Body>, season: &str) -> Result<Value, StatusCode> {
+        let query = Query::parse(req.uri().query());
+        if !query.views.iter().any(|v| v == "proTeamSchedules_wl") {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        read_json(&self.fixtures.join(season).join("pro_team_schedules.json"))
+            .await?
+            .ok_or(StatusCode::NOT_FOUND)
+    }
+}
+
+enum Route {
+    League { season: String, league_id: String },
+    Season { season: String },
+}
+
+fn route(path: &str) -> Option<Route> {
+    let rest = path.strip_prefix(API_PATH)?.trim_matches('/');
+    let segments: Vec<_> = rest.split('/').collect();
+    match segments.as_slice() {
+        ["seasons", season, "segments", "0", "leagues", league_id] => Some(Route::League {
+            season: season.to_string(),
+            league_id: league_id.to_string(),
+        }),
+        ["seasons", season] => Some(Route::Season {
+            season: season.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Query {
+    views: Vec<String>,
+    scoring_period_id: Option<u8>,
+}
+
+impl Query {
+    fn parse(query: Option<&str>) -> Query {
+        let mut parsed = Query::default();
+        for pair in query.unwrap_or_default().split('&') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            match (percent_decode(key).as_str(), percent_decode(value)) {
+                ("view", view) => parsed.views.push(view),
+                ("scoringPeriodId", sp) => parsed.scoring_period_id = sp.parse().ok(),
+                _ => {}
+            }
+        }
+        parsed
+    }
+}
+
+/// Decodes `%XX` escapes and `+` for spaces in a query string component. Malformed escapes are
+/// kept as they are.
+fn percent_decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match component
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Reads a fixture, or `None` when there isn't one. A fixture that isn't valid JSON is a 500.
+async fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, StatusCode> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR),
+        Err(_) => Ok(None),
+    }
+}
+
+fn authorized(req: &Request<Body>, auth: &Auth) -> bool {
+    let cookies: HashSet<(&str, &str)> = req
+        .headers()
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|c| c.to_str().ok())
+        .flat_map(|c| c.split(';'))
+        .filter_map(|c| c.trim().split_once('='))
+        .collect();
+    cookies.contains(&("SWID", auth.swid.as_str()))
+        && cookies.contains(&("espn_s2", auth.espn_s2.as_str()))
+}
+
+/// Keeps only the fields the requested views return. Teams carry their roster only for `mRoster`,
+/// and only their id and roster without `mTeam`.
+fn select_views(league: Value, views: &[String]) -> Value {
+    let Value::Object(league) = league else {
+        return league;
+    };
+    let fields: HashSet<&str> = views
+        .iter()
+        .flat_map(|v| view_fields(v).iter().copied())
+        .chain(ALWAYS_INCLUDED)
+        .collect();
+    let has_view = |view: &str| views.iter().any(|v| v == view);
+    let mut response: Map<String, Value> = league
+        .into_iter()
+        .filter(|(k, _)| fields.contains(k.as_str()))
+        .collect();
+    if let Some(Value::Array(teams)) = response.get_mut("teams") {
+        for team in teams.iter_mut().filter_map(Value::as_object_mut) {
+            if !has_view("mRoster") {
+                team.remove("roster");
+            }
+            if !has_view("mTeam") {
+                team.retain(|k, _| k == "id" || k == "roster");
+            }
+        }
+    }
+    if !has_view("mTeam") {
+        response.remove("members");
+    }
+    Value::Object(response)
+}
+
+/// Drops stat lines for other scoring periods, keeping season totals, the way ESPN does when a
+/// scoring period is requested.
+fn filter_stats(value: &mut Value, scoring_period_id: u8) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::Array(stats)) = map.get_mut("stats") {
+                stats.retain(|s| match s.get("scoringPeriodId").and_then(Value::as_u64) {
+                    Some(sp) => sp == 0 || sp == scoring_period_id as u64,
+                    None => true,
+                });
+            }
+            map.values_mut()
+                .for_each(|v| filter_stats(v, scoring_period_id));
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|v| filter_stats(v, scoring_period_id)),
+        _ => {}
+    }
+}
+
+/// Evaluates the parts of an `x-fantasy-filter` this crate sends against the `players` pool:
+/// `filterStatus`, `filterIds`, `filterSlotIds`, `sortPercOwned`, `offset` and `limit`.
+fn apply_filter(response: &mut Value, filter: &Value) {
+    let (Some(Value::Array(players)), Some(filter)) =
+        (response.get_mut("players"), filter.get("players"))
+    else {
+        return;
+    };
+    let values =
+        |key: &str| -> Option<Vec<Value>> { filter.get(key)?.get("value")?.as_array().cloned() };
+    if let Some(statuses) = values("filterStatus") {
+        players.retain(|p| p.get("status").is_some_and(|s| statuses.contains(s)));
+    }
+    if let Some(ids) = values("filterIds") {
+        players.retain(|p| p.get("id").is_some_and(|id| ids.contains(id)));
+    }
+    if let Some(slots) = values("filterSlotIds") {
+        players.retain(|p| {
+            p.pointer("/player/eligibleSlots")
+                .and_then(Value::as_array)
+                .is_some_and(|eligible| eligible.iter().any(|s| slots.contains(s)))
+        });
+    }
+    if let Some(sort) = filter.get("sortPercOwned") {
+        let percent_owned = |p: &Value| {
+            p.pointer("/player/ownership/percentOwned")
+                .and_then(Value::as_f64)
+                .unwrap_or_default()
+        };
+        players.sort_by(|a, b| percent_owned(a).total_cmp(&percent_owned(b)));
+        if !sort.get("sortAsc").and_then(Value::as_bool).unwrap_or(true) {
+            players.reverse();
+        }
+    }
+    let offset = filter.get("offset").and_then(Value::as_u64).unwrap_or(0) as usize;
+    players.drain(..offset.min(players.len()));
+    if let Some(limit) = filter.get("limit").and_then(Value::as_u64) {
+        players.truncate(limit as usize);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        client::EspnClient,
+        source::{DataSource, EspnRequest, FetchError, HttpSource},
+    };
+    use serde_json::json;
+
+    fn fixtures() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("espn-mock-{}", std::process::id()));
+        let league = dir.join("2023").join("1234");
+        std::fs::create_dir_all(&league).unwrap();
+        let player = |id: u64, status: &str, owned: f64| {
+            json!({
+                "id": id,
+                "status": status,
+                "player": { "eligibleSlots": [2], "ownership": { "percentOwned": owned } }
+            })
+        };
+        let fixture = json!({
+            "gameId": 1,
+            "id": 1234,
+            "seasonId": 2023,
+            "segmentId": 0,
+            "scoringPeriodId": 1,
+            "status": { "currentMatchupPeriod": 1 },
+            "settings": {},
+            "teams": [{
+                "id": 1,
+                "abbrev": "TM",
+                "location": "Mock",
+                "nickname": "Team",
+                "roster": { "entries": [] }
+            }],
+            "members": [],
+            "schedule": [],
+            "players": [
+                player(1, "FREEAGENT", 10.0),
+                player(2, "ONTEAM", 90.0),
+                player(3, "WAIVERS", 50.0),
+                player(4, "FREEAGENT", 5.0),
+            ],
+        });
+        std::fs::write(league.join("league.json"), fixture.to_string()).unwrap();
+        let private = dir.join("2023").join("99");
+        std::fs::create_dir_all(&private).unwrap();
+        std::fs::write(private.join("league.json"), fixture.to_string()).unwrap();
+        std::fs::write(
+            private.join("auth.json"),
+            json!({ "swid": "{SWID}", "espn_s2": "s2" }).to_string(),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn serves_fixtures_like_espn() {
+        let dir = fixtures();
+        let addr = MockServer::new(&dir).spawn();
+        let source = HttpSource::with_base_url(&MockServer::base_url(addr), "", "");
+        let fetch = |req: EspnRequest| {
+            let source = &source;
+            async move {
+                serde_json::from_str::<Value>(&source.fetch(&req).await?)
+                    .map_err(|e| FetchError::Deserialize(e.to_string()))
+            }
+        };
+
+        let teams = fetch(
+            EspnRequest::league(2023, 1234)
+                .view("mTeam")
+                .scoring_period(3),
+        )
+        .await
+        .unwrap();
+        assert_eq!(teams["scoringPeriodId"], 3);
+        assert_eq!(teams["teams"][0]["nickname"], "Team");
+        assert!(teams["teams"][0].get("roster").is_none());
+        assert!(teams.get("settings").is_none());
+
+        let free_agents = fetch(
+            EspnRequest::league(2023, 1234)
+                .view("kona_player_info")
+                .filter(json!({ "players": {
+                    "filterStatus": { "value": ["FREEAGENT", "WAIVERS"] },
+                    "limit": 2,
+                    "sortPercOwned": { "sortAsc": false, "sortPriority": 1 }
+                }})),
+        )
+        .await
+        .unwrap();
+        let ids: Vec<_> = free_agents["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["id"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ids, vec![3, 1]);
+
+        assert_eq!(
+            fetch(EspnRequest::league(2023, 4321).view("mTeam")).await,
+            Err(FetchError::NotFound)
+        );
+        assert_eq!(
+            fetch(EspnRequest::league(2023, 99).view("mTeam")).await,
+            Err(FetchError::Unauthorized)
+        );
+        let private = HttpSource::with_base_url(&MockServer::base_url(addr), "{SWID}", "s2");
+        // The cookies stay with the host they were given for.
+        let elsewhere = HttpSource {
+            client: private.client.clone(),
+            base_url: format!("http://localhost:{}{}", addr.port(), API_PATH),
+        };
+        assert_eq!(
+            elsewhere
+                .fetch(&EspnRequest::league(2023, 99).view("mTeam"))
+                .await,
+            Err(FetchError::Unauthorized)
+        );
+        let client = EspnClient::with_source(99, Arc::new(private));
+        assert!(client.get_league_members(2023).await.unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn decodes_query_strings() {
+        let query = Query::parse(Some(
+            "view=mTeam&%76iew=kona%5Fplayer%5Finfo&scoringPeriodId=%31%32",
+        ));
+        assert_eq!(query.views, vec!["mTeam", "kona_player_info"]);
+        assert_eq!(query.scoring_period_id, Some(12));
+        assert_eq!(percent_decode("a+b%2Fc%zz%"), "a b/c%zz%");
+    }
+}
@@ -1,4 +1,8 @@
-use crate::id_maps::{PositionId, ProTeamId};
+use crate::id_maps::ProTeamId;
+use crate::league::PlayerRankType;
+use crate::position::{LineupSlot, Position};
+use crate::status::InjuryStatus;
+use crate::timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, hash::Hash};
 
@@ -11,13 +15,13 @@ pub struct PlayerId(pub i64);
 pub struct Player {
     pub active: bool,
     #[serde(rename = "defaultPositionId")]
-    pub default_position_id: PositionId,
+    pub default_position_id: Position,
     #[serde(rename = "draftRanksByRankType")]
     pub draft_ranks_by_rank_type: Option<HashMap<String, Ranking>>,
     pub droppable: bool,
     /// The positions a player is eligible to play in.
     #[serde(rename = "eligibleSlots")]
-    pub eligible_slots: Vec<PositionId>,
+    pub eligible_slots: Vec<LineupSlot>,
     #[serde(rename = "firstName")]
     pub first_name: String,
     #[serde(rename = "fullName")]
@@ -25,14 +29,14 @@ pub struct Player {
     pub id: PlayerId,
     pub injured: bool,
     #[serde(rename = "injuryStatus")]
-    pub injury_status: Option<String>,
+    pub injury_status: Option<InjuryStatus>,
     pub jersey: Option<String>,
     #[serde(rename = "lastName")]
     pub last_name: String,
     #[serde(rename = "lastNewsDate")]
-    pub last_news_date: Option<u64>,
+    pub last_news_date: Option<Timestamp>,
     #[serde(rename = "lastVideoDate")]
-    pub last_video_date: Option<u64>,
+    pub last_video_date: Option<Timestamp>,
     pub outlooks: Option<Outlooks>,
     pub ownership: Option<Ownership>,
     #[serde(rename = "proTeamId")]
@@ -44,6 +48,90 @@ pub struct Player {
     #[serde(rename = "universeId")]
     pub universe_id: Option<u32>,
 }
+impl Player {
+    /// Whether the player is active and not ruled out by an injury or suspension.
+    pub fn is_startable(&self) -> bool {
+        self.active && self.injury_status.as_ref().is_none_or(|s| s.is_available())
+    }
+    /// The stat line from a source for a single scoring period of the season, or for the whole
+    /// season when `scoring_period_id` is `None`.
+    ///
+    /// Players often carry lines from other seasons too, such as last season's totals early in
+    /// the next, so the season has to match.
+    pub fn stat_line(
+        &self,
+        source: StatSource,
+        season: u16,
+        scoring_period_id: Option<u8>,
+    ) -> Option<&Stats> {
+        self.stats.as_ref()?.iter().find(|s| {
+            s.source() == source
+                && s.season_id == season as u32
+                && match scoring_period_id {
+                    Some(period) => {
+                        s.split() == StatSplit::ScoringPeriod && s.scoring_period_id == period
+                    }
+                    None => s.split() == StatSplit::Season,
+                }
+        })
+    }
+    /// Fantasy points scored in a scoring period.
+    pub fn actual_points(&self, season: u16, scoring_period_id: u8) -> Option<f32> {
+        self.stat_line(StatSource::Actual, season, Some(scoring_period_id))
+            .map(|s| s.applied_total)
+    }
+    /// Projected fantasy points for a single scoring period, if ESPN supplied a projection for it.
+    pub fn projected_points(&self, season: u16, scoring_period_id: u8) -> Option<f32> {
+        self.stat_line(StatSource::Projected, season, Some(scoring_period_id))
+            .map(|s| s.applied_total)
+    }
+    /// The player's preseason draft ranking under the league's rank type.
+    pub fn draft_rank(&self, rank_type: &PlayerRankType) -> Option<&Ranking> {
+        self.draft_ranks_by_rank_type
+            .as_ref()?
+            .get(rank_type.as_str())
+    }
+    /// The average projected fantasy points per game over the whole season.
+    pub fn projected_average(&self, season: u16) -> Option<f32> {
+        self.stat_line(StatSource::Projected, season, None)
+            .and_then(|s| s.applied_average)
+    }
+}
+
+/// Whether a stat line records what happened or what ESPN expects to happen.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatSource {
+    Actual,
+    Projected,
+    Other(u32),
+}
+impl From<u32> for StatSource {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => StatSource::Actual,
+            1 => StatSource::Projected,
+            v => StatSource::Other(v),
+        }
+    }
+}
+
+/// The span of games a stat line covers.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatSplit {
+    Season,
+    ScoringPeriod,
+    Other(u32),
+}
+impl From<u32> for StatSplit {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => StatSplit::Season,
+            1 => StatSplit::ScoringPeriod,
+            v => StatSplit::Other(v),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
 pub struct Stats {
     #[serde(rename = "appliedAverage")]
@@ -68,6 +156,14 @@ pub struct Stats {
     pub stats: HashMap<u16, f32>,
     pub variance: Option<HashMap<u16, f32>>,
 }
+impl Stats {
+    pub fn source(&self) -> StatSource {
+        self.stat_source_id.into()
+    }
+    pub fn split(&self) -> StatSplit {
+        self.stat_split_type_id.into()
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
 pub struct Ranking {
@@ -79,7 +175,7 @@ pub struct Ranking {
     #[serde(rename = "rankType")]
     pub rank_type: String,
     #[serde(rename = "slotId")]
-    pub slot_id: PositionId,
+    pub slot_id: LineupSlot,
 }
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
@@ -100,7 +196,7 @@ pub struct Ownership {
     pub average_draft_position: f32,
     #[serde(rename = "averageDraftPositionPercentChange")]
     pub average_draft_position_percent_change: f32,
-    pub date: u64,
+    pub date: Timestamp,
     #[serde(rename = "leagueType")]
     pub league_type: i8,
     #[serde(rename = "percentChange")]
@@ -110,3 +206,20 @@ pub struct Ownership {
     #[serde(rename = "percentStarted")]
     pub percent_started: f32,
 }
+
+/// Players looked up by id with the `kona_playercard` view, as returned by
+/// `EspnClient::get_player_cards`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PlayerCardResponse {
+    pub players: Vec<PlayerCard>,
+}
+
+/// A player's card: their stat lines for recent scoring periods and seasons, whoever owns them.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PlayerCard {
+    pub id: PlayerId,
+    /// Zero when the player is a free agent.
+    #[serde(rename = "onTeamId")]
+    pub on_team_id: u8,
+    pub player: Player,
+}
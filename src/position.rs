@@ -0,0 +1,424 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use super::registry;
+
+/// A player's position, as given by their `defaultPositionId`.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(from = "u64", into = "u64")]
+pub enum Position {
+    Quarterback,
+    RunningBack,
+    WideReceiver,
+    TightEnd,
+    Kicker,
+    Punter,
+    DefensiveTackle,
+    DefensiveEnd,
+    Linebacker,
+    Cornerback,
+    Safety,
+    HeadCoach,
+    TeamDefense,
+    /// A position id not yet known to this crate.
+    Other(u64),
+}
+
+// Adapted from https://github.com/cwendt94/espn-api/blob/master/espn_api/football/constant.py
+impl Position {
+    pub const ALL: [Position; 13] = [
+        Position::Quarterback,
+        Position::RunningBack,
+        Position::WideReceiver,
+        Position::TightEnd,
+        Position::Kicker,
+        Position::Punter,
+        Position::DefensiveTackle,
+        Position::DefensiveEnd,
+        Position::Linebacker,
+        Position::Cornerback,
+        Position::Safety,
+        Position::HeadCoach,
+        Position::TeamDefense,
+    ];
+
+    pub fn id(&self) -> u64 {
+        match self {
+            Position::Quarterback => 1,
+            Position::RunningBack => 2,
+            Position::WideReceiver => 3,
+            Position::TightEnd => 4,
+            Position::Kicker => 5,
+            Position::Punter => 7,
+            Position::DefensiveTackle => 9,
+            Position::DefensiveEnd => 10,
+            Position::Linebacker => 11,
+            Position::Cornerback => 12,
+            Position::Safety => 13,
+            Position::HeadCoach => 14,
+            Position::TeamDefense => 16,
+            Position::Other(id) => *id,
+        }
+    }
+
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Position::Quarterback => "QB",
+            Position::RunningBack => "RB",
+            Position::WideReceiver => "WR",
+            Position::TightEnd => "TE",
+            Position::Kicker => "K",
+            Position::Punter => "P",
+            Position::DefensiveTackle => "DT",
+            Position::DefensiveEnd => "DE",
+            Position::Linebacker => "LB",
+            Position::Cornerback => "CB",
+            Position::Safety => "S",
+            Position::HeadCoach => "HC",
+            Position::TeamDefense => "D/ST",
+            Position::Other(id) => registry::position(*id).unwrap_or("Unknown"),
+        }
+    }
+
+    /// Whether the position is an individual defensive player.
+    pub fn is_idp(&self) -> bool {
+        matches!(
+            self,
+            Position::DefensiveTackle
+                | Position::DefensiveEnd
+                | Position::Linebacker
+                | Position::Cornerback
+                | Position::Safety
+        )
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::Other(0)
+    }
+}
+
+impl From<u64> for Position {
+    fn from(id: u64) -> Self {
+        Position::ALL
+            .into_iter()
+            .find(|p| p.id() == id)
+            .unwrap_or(Position::Other(id))
+    }
+}
+
+impl From<Position> for u64 {
+    fn from(position: Position) -> Self {
+        position.id()
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Position::Other(id) => match registry::position(*id) {
+                Some(abbreviation) => write!(f, "{}", abbreviation),
+                None => write!(f, "Position({})", id),
+            },
+            p => write!(f, "{}", p.abbreviation()),
+        }
+    }
+}
+
+impl FromStr for Position {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_uppercase();
+        match Position::ALL.into_iter().find(|p| p.abbreviation() == s) {
+            Some(p) => Ok(p),
+            None if s == "DST" || s == "DEF" => Ok(Position::TeamDefense),
+            None => registry::position_by_abbreviation(&s).ok_or(format!("Unknown position {}", s)),
+        }
+    }
+}
+
+/// A place in a fantasy lineup, as given by a `lineupSlotId`.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[serde(from = "u64", into = "u64")]
+pub enum LineupSlot {
+    Quarterback,
+    TeamQuarterback,
+    RunningBack,
+    RunningBackWideReceiver,
+    WideReceiver,
+    WideReceiverTightEnd,
+    TightEnd,
+    /// Offensive player, also known as a superflex.
+    OffensivePlayer,
+    DefensiveTackle,
+    DefensiveEnd,
+    Linebacker,
+    DefensiveLineman,
+    Cornerback,
+    Safety,
+    DefensiveBack,
+    /// Any individual defensive player.
+    DefensivePlayer,
+    TeamDefense,
+    Kicker,
+    Punter,
+    HeadCoach,
+    #[default]
+    Bench,
+    InjuredReserve,
+    /// The standard flex slot.
+    RunningBackWideReceiverTightEnd,
+    EdgeRusher,
+    /// A slot id not yet known to this crate.
+    Other(u64),
+}
+
+impl LineupSlot {
+    pub const ALL: [LineupSlot; 24] = [
+        LineupSlot::Quarterback,
+        LineupSlot::TeamQuarterback,
+        LineupSlot::RunningBack,
+        LineupSlot::RunningBackWideReceiver,
+        LineupSlot::WideReceiver,
+        LineupSlot::WideReceiverTightEnd,
+        LineupSlot::TightEnd,
+        LineupSlot::OffensivePlayer,
+        LineupSlot::DefensiveTackle,
+        LineupSlot::DefensiveEnd,
+        LineupSlot::Linebacker,
+        LineupSlot::DefensiveLineman,
+        LineupSlot::Cornerback,
+        LineupSlot::Safety,
+        LineupSlot::DefensiveBack,
+        LineupSlot::DefensivePlayer,
+        LineupSlot::TeamDefense,
+        LineupSlot::Kicker,
+        LineupSlot::Punter,
+        LineupSlot::HeadCoach,
+        LineupSlot::Bench,
+        LineupSlot::InjuredReserve,
+        LineupSlot::RunningBackWideReceiverTightEnd,
+        LineupSlot::EdgeRusher,
+    ];
+
+    pub fn id(&self) -> u64 {
+        match self {
+            LineupSlot::Quarterback => 0,
+            LineupSlot::TeamQuarterback => 1,
+            LineupSlot::RunningBack => 2,
+            LineupSlot::RunningBackWideReceiver => 3,
+            LineupSlot::WideReceiver => 4,
+            LineupSlot::WideReceiverTightEnd => 5,
+            LineupSlot::TightEnd => 6,
+            LineupSlot::OffensivePlayer => 7,
+            LineupSlot::DefensiveTackle => 8,
+            LineupSlot::DefensiveEnd => 9,
+            LineupSlot::Linebacker => 10,
+            LineupSlot::DefensiveLineman => 11,
+            LineupSlot::Cornerback => 12,
+            LineupSlot::Safety => 13,
+            LineupSlot::DefensiveBack => 14,
+            LineupSlot::DefensivePlayer => 15,
+            LineupSlot::TeamDefense => 16,
+            LineupSlot::Kicker => 17,
+            LineupSlot::Punter => 18,
+            LineupSlot::HeadCoach => 19,
+            LineupSlot::Bench => 20,
+            LineupSlot::InjuredReserve => 21,
+            LineupSlot::RunningBackWideReceiverTightEnd => 23,
+            LineupSlot::EdgeRusher => 24,
+            LineupSlot::Other(id) => *id,
+        }
+    }
+
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            LineupSlot::Quarterback => "QB",
+            LineupSlot::TeamQuarterback => "TQB",
+            LineupSlot::RunningBack => "RB",
+            LineupSlot::RunningBackWideReceiver => "RB/WR",
+            LineupSlot::WideReceiver => "WR",
+            LineupSlot::WideReceiverTightEnd => "WR/TE",
+            LineupSlot::TightEnd => "TE",
+            LineupSlot::OffensivePlayer => "OP",
+            LineupSlot::DefensiveTackle => "DT",
+            LineupSlot::DefensiveEnd => "DE",
+            LineupSlot::Linebacker => "LB",
+            LineupSlot::DefensiveLineman => "DL",
+            LineupSlot::Cornerback => "CB",
+            LineupSlot::Safety => "S",
+            LineupSlot::DefensiveBack => "DB",
+            LineupSlot::DefensivePlayer => "DP",
+            LineupSlot::TeamDefense => "D/ST",
+            LineupSlot::Kicker => "K",
+            LineupSlot::Punter => "P",
+            LineupSlot::HeadCoach => "HC",
+            LineupSlot::Bench => "Bench",
+            LineupSlot::InjuredReserve => "IR",
+            LineupSlot::RunningBackWideReceiverTightEnd => "RB/WR/TE",
+            LineupSlot::EdgeRusher => "ER",
+            LineupSlot::Other(_) => "Unknown",
+        }
+    }
+
+    /// The positions that may be played in this slot.
+    pub fn accepts(&self) -> &'static [Position] {
+        use Position::*;
+        match self {
+            LineupSlot::Quarterback | LineupSlot::TeamQuarterback => &[Quarterback],
+            LineupSlot::RunningBack => &[RunningBack],
+            LineupSlot::RunningBackWideReceiver => &[RunningBack, WideReceiver],
+            LineupSlot::WideReceiver => &[WideReceiver],
+            LineupSlot::WideReceiverTightEnd => &[WideReceiver, TightEnd],
+            LineupSlot::TightEnd => &[TightEnd],
+            LineupSlot::OffensivePlayer => &[Quarterback, RunningBack, WideReceiver, TightEnd],
+            LineupSlot::DefensiveTackle => &[DefensiveTackle],
+            LineupSlot::DefensiveEnd => &[DefensiveEnd],
+            LineupSlot::Linebacker => &[Linebacker],
+            LineupSlot::DefensiveLineman => &[DefensiveTackle, DefensiveEnd],
+            LineupSlot::Cornerback => &[Cornerback],
+            LineupSlot::Safety => &[Safety],
+            LineupSlot::DefensiveBack => &[Cornerback, Safety],
+            LineupSlot::DefensivePlayer => &[
+                DefensiveTackle,
+                DefensiveEnd,
+                Linebacker,
+                Cornerback,
+                Safety,
+            ],
+            LineupSlot::TeamDefense => &[TeamDefense],
+            LineupSlot::Kicker => &[Kicker],
+            LineupSlot::Punter => &[Punter],
+            LineupSlot::HeadCoach => &[HeadCoach],
+            LineupSlot::Bench | LineupSlot::InjuredReserve => &Position::ALL,
+            LineupSlot::RunningBackWideReceiverTightEnd => &[RunningBack, WideReceiver, TightEnd],
+            LineupSlot::EdgeRusher => &[DefensiveEnd, Linebacker],
+            LineupSlot::Other(_) => &[],
+        }
+    }
+
+    pub fn accepts_position(&self, position: &Position) -> bool {
+        self.accepts().contains(position)
+    }
+
+    /// Whether points scored in this slot count towards the team's score.
+    pub fn is_starter(&self) -> bool {
+        !matches!(
+            self,
+            LineupSlot::Bench | LineupSlot::InjuredReserve | LineupSlot::Other(_)
+        )
+    }
+
+    /// Whether this is a starting slot that can be filled by more than one position.
+    pub fn is_flex(&self) -> bool {
+        self.is_starter() && self.accepts().len() > 1
+    }
+
+    pub fn is_bench(&self) -> bool {
+        matches!(self, LineupSlot::Bench)
+    }
+
+    pub fn is_injured_reserve(&self) -> bool {
+        matches!(self, LineupSlot::InjuredReserve)
+    }
+}
+
+impl From<u64> for LineupSlot {
+    fn from(id: u64) -> Self {
+        LineupSlot::ALL
+            .into_iter()
+            .find(|s| s.id() == id)
+            .unwrap_or(LineupSlot::Other(id))
+    }
+}
+
+impl From<LineupSlot> for u64 {
+    fn from(slot: LineupSlot) -> Self {
+        slot.id()
+    }
+}
+
+impl fmt::Display for LineupSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineupSlot::Other(id) => write!(f, "Slot({})", id),
+            s => write!(f, "{}", s.abbreviation()),
+        }
+    }
+}
+
+impl FromStr for LineupSlot {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_uppercase();
+        match LineupSlot::ALL
+            .into_iter()
+            .find(|slot| slot.abbreviation().to_uppercase() == s)
+        {
+            Some(slot) => Ok(slot),
+            None => match s.as_str() {
+                "FLEX" => Ok(LineupSlot::RunningBackWideReceiverTightEnd),
+                "BE" => Ok(LineupSlot::Bench),
+                "DST" | "DEF" => Ok(LineupSlot::TeamDefense),
+                _ => Err(format!("Unknown lineup slot {}", s)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slots_convert_to_and_from_strings() {
+        assert_eq!(LineupSlot::from(0).to_string(), "QB");
+        assert_eq!(LineupSlot::from(16).to_string(), "D/ST");
+        assert_eq!(LineupSlot::from(24).to_string(), "ER");
+        assert_eq!(LineupSlot::from(22).to_string(), "Slot(22)");
+        assert_eq!(
+            "flex".parse(),
+            Ok(LineupSlot::RunningBackWideReceiverTightEnd)
+        );
+        assert_eq!(
+            "RB/WR/TE".parse(),
+            Ok(LineupSlot::RunningBackWideReceiverTightEnd)
+        );
+        assert!("XYZ".parse::<LineupSlot>().is_err());
+    }
+
+    #[test]
+    fn positions_use_default_position_ids() {
+        assert_eq!(Position::from(1), Position::Quarterback);
+        assert_eq!(Position::from(16).to_string(), "D/ST");
+        assert_eq!("wr".parse(), Ok(Position::WideReceiver));
+        assert_eq!(Position::from(6), Position::Other(6));
+    }
+
+    #[test]
+    fn slots_know_what_they_accept() {
+        assert!(LineupSlot::RunningBackWideReceiverTightEnd.is_flex());
+        assert!(LineupSlot::OffensivePlayer.is_flex());
+        assert!(LineupSlot::DefensivePlayer.is_flex());
+        assert!(!LineupSlot::TightEnd.is_flex());
+        assert!(!LineupSlot::Bench.is_starter());
+        assert!(!LineupSlot::Bench.is_flex());
+        assert!(LineupSlot::OffensivePlayer.accepts_position(&Position::Quarterback));
+        assert!(
+            !LineupSlot::RunningBackWideReceiverTightEnd.accepts_position(&Position::Quarterback)
+        );
+    }
+
+    #[test]
+    fn map_keys_round_trip() {
+        let counts: std::collections::HashMap<LineupSlot, i8> =
+            serde_json::from_str(r#"{"0": 1, "23": 2, "99": 0}"#).unwrap();
+        assert_eq!(counts[&LineupSlot::Quarterback], 1);
+        assert_eq!(counts[&LineupSlot::RunningBackWideReceiverTightEnd], 2);
+        assert_eq!(counts[&LineupSlot::Other(99)], 0);
+        let json = serde_json::to_value(&counts).unwrap();
+        assert_eq!(json["23"], 2);
+    }
+}
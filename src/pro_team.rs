@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{id_maps::ProTeamId, timestamp::Timestamp};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProTeamScheduleResponse {
+    pub settings: ProTeamSettings,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProTeamSettings {
+    #[serde(rename = "proTeams")]
+    pub pro_teams: Vec<ProTeam>,
+}
+
+/// An NFL team and its games for the season.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProTeam {
+    pub abbrev: String,
+    #[serde(rename = "byeWeek")]
+    pub bye_week: u8,
+    pub id: ProTeamId,
+    pub location: String,
+    pub name: String,
+    #[serde(rename = "proGamesByScoringPeriod")]
+    pub pro_games_by_scoring_period: Option<HashMap<u8, Vec<ProGame>>>,
+}
+
+impl ProTeam {
+    /// The team's game in a scoring period, or `None` on a bye.
+    pub fn game(&self, scoring_period_id: u8) -> Option<&ProGame> {
+        self.pro_games_by_scoring_period
+            .as_ref()?
+            .get(&scoring_period_id)?
+            .first()
+    }
+
+    /// The team's opponent in a scoring period, or `None` on a bye.
+    pub fn opponent(&self, scoring_period_id: u8) -> Option<ProTeamId> {
+        let game = self.game(scoring_period_id)?;
+        if game.home_pro_team_id == self.id {
+            Some(game.away_pro_team_id.clone())
+        } else {
+            Some(game.home_pro_team_id.clone())
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProGame {
+    #[serde(rename = "awayProTeamId")]
+    pub away_pro_team_id: ProTeamId,
+    pub date: Timestamp,
+    #[serde(rename = "homeProTeamId")]
+    pub home_pro_team_id: ProTeamId,
+    pub id: u64,
+    #[serde(rename = "scoringPeriodId")]
+    pub scoring_period_id: u8,
+    #[serde(rename = "statsOfficial")]
+    pub stats_official: bool,
+    #[serde(rename = "validForLocking")]
+    pub valid_for_locking: bool,
+}
+
+/// How long after kickoff a game is assumed to be over while ESPN hasn't marked its stats official.
+const GAME_LENGTH_MILLIS: i64 = 4 * 60 * 60 * 1000;
+
+impl ProGame {
+    pub fn status(&self, now: impl Into<Timestamp>) -> GameStatus {
+        let now = now.into();
+        if self.stats_official {
+            GameStatus::Final
+        } else if now < self.date {
+            GameStatus::NotStarted
+        } else if now.millis() < self.date.millis() + GAME_LENGTH_MILLIS {
+            GameStatus::InProgress
+        } else {
+            GameStatus::Unofficial
+        }
+    }
+}
+
+/// Where an NFL team's game stands.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GameStatus {
+    NotStarted,
+    InProgress,
+    /// Probably over, going by the kickoff time, but ESPN hasn't made the stats official.
+    Unofficial,
+    Final,
+    Bye,
+}
+
+impl GameStatus {
+    /// Whether players in this game can still score.
+    pub fn is_remaining(&self) -> bool {
+        matches!(self, GameStatus::NotStarted | GameStatus::InProgress)
+    }
+
+    /// Whether the game's stats are settled: official, or there's no game.
+    pub fn is_settled(&self) -> bool {
+        matches!(self, GameStatus::Final | GameStatus::Bye)
+    }
+}
+
+/// Each NFL team's game in a scoring period. Teams on bye are left out.
+pub fn games_for_week(pro_teams: &[ProTeam], scoring_period_id: u8) -> HashMap<ProTeamId, ProGame> {
+    pro_teams
+        .iter()
+        .filter_map(|t| Some((t.id.clone(), t.game(scoring_period_id)?.clone())))
+        .collect()
+}
+
+/// Who each NFL team plays in a scoring period. Teams on bye are left out.
+pub fn opponents_for_week(
+    pro_teams: &[ProTeam],
+    scoring_period_id: u8,
+) -> HashMap<ProTeamId, ProTeamId> {
+    pro_teams
+        .iter()
+        .filter_map(|t| Some((t.id.clone(), t.opponent(scoring_period_id)?)))
+        .collect()
+}
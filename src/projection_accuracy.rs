@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{player::PlayerId, position::Position, team::Team};
+
+/// A player's projection for a scoring period alongside what they actually scored.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ProjectionSample {
+    pub player_id: PlayerId,
+    pub player_name: String,
+    pub position: Position,
+    pub scoring_period_id: u8,
+    pub projected: f32,
+    pub actual: f32,
+}
+
+impl ProjectionSample {
+    /// Positive when ESPN projected more points than were scored.
+    pub fn error(&self) -> f32 {
+        self.projected - self.actual
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct ErrorSummary {
+    pub count: usize,
+    pub mean_absolute_error: f32,
+    /// Mean of projected minus actual points; positive means projections ran high.
+    pub bias: f32,
+}
+
+impl ErrorSummary {
+    pub fn from_samples<'a>(
+        samples: impl IntoIterator<Item = &'a ProjectionSample>,
+    ) -> ErrorSummary {
+        let mut summary = ErrorSummary::default();
+        let mut absolute = 0.0;
+        let mut total = 0.0;
+        for sample in samples {
+            summary.count += 1;
+            absolute += sample.error().abs();
+            total += sample.error();
+        }
+        if summary.count > 0 {
+            summary.mean_absolute_error = absolute / summary.count as f32;
+            summary.bias = total / summary.count as f32;
+        }
+        summary
+    }
+}
+
+/// How well ESPN's projections matched the points actually scored.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct AccuracyReport {
+    pub overall: ErrorSummary,
+    pub by_position: HashMap<Position, ErrorSummary>,
+    pub by_week: HashMap<u8, ErrorSummary>,
+    pub samples: Vec<ProjectionSample>,
+}
+
+impl AccuracyReport {
+    pub fn from_samples(samples: Vec<ProjectionSample>) -> AccuracyReport {
+        let mut positions: HashMap<Position, Vec<&ProjectionSample>> = HashMap::new();
+        let mut weeks: HashMap<u8, Vec<&ProjectionSample>> = HashMap::new();
+        for sample in &samples {
+            positions.entry(sample.position).or_default().push(sample);
+            weeks
+                .entry(sample.scoring_period_id)
+                .or_default()
+                .push(sample);
+        }
+        AccuracyReport {
+            overall: ErrorSummary::from_samples(&samples),
+            by_position: positions
+                .into_iter()
+                .map(|(position, s)| (position, ErrorSummary::from_samples(s)))
+                .collect(),
+            by_week: weeks
+                .into_iter()
+                .map(|(week, s)| (week, ErrorSummary::from_samples(s)))
+                .collect(),
+            samples,
+        }
+    }
+}
+
+/// Projected and actual points for every rostered player who has both in the scoring period of
+/// the season.
+///
+/// The teams must include rosters, as returned by `get_teams_at_week`.
+pub fn samples_from_teams(
+    teams: &[Team],
+    season: u16,
+    scoring_period_id: u8,
+) -> Vec<ProjectionSample> {
+    teams
+        .iter()
+        .filter_map(|t| t.roster.as_ref())
+        .flat_map(|r| r.entries.iter())
+        .filter_map(|entry| {
+            let player = &entry.player_pool_entry.player;
+            Some(ProjectionSample {
+                player_id: player.id,
+                player_name: player.full_name.clone(),
+                position: player.default_position_id,
+                scoring_period_id,
+                projected: player.projected_points(season, scoring_period_id)?,
+                actual: player.actual_points(season, scoring_period_id)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::synthetic::LeagueGenerator;
+
+    fn sample(position: u64, week: u8, projected: f32, actual: f32) -> ProjectionSample {
+        ProjectionSample {
+            player_id: PlayerId(1),
+            player_name: "Player".to_string(),
+            position: Position::from(position),
+            scoring_period_id: week,
+            projected,
+            actual,
+        }
+    }
+
+    #[test]
+    fn summarizes_error_and_bias() {
+        let report = AccuracyReport::from_samples(vec![
+            sample(1, 1, 20.0, 10.0),
+            sample(1, 2, 10.0, 20.0),
+            sample(2, 1, 15.0, 12.0),
+        ]);
+        assert_eq!(report.overall.count, 3);
+        assert!((report.overall.mean_absolute_error - 23.0 / 3.0).abs() < 1e-5);
+        assert!((report.overall.bias - 1.0).abs() < 1e-5);
+        let qb = &report.by_position[&Position::Quarterback];
+        assert_eq!(qb.count, 2);
+        assert_eq!(qb.bias, 0.0);
+        assert_eq!(report.by_week[&1].bias, 6.5);
+    }
+
+    #[test]
+    fn samples_come_from_the_requested_season() {
+        let mut generator = LeagueGenerator::new(3);
+        generator.weeks_played = Some(4);
+        let league = generator.generate();
+        assert!(!samples_from_teams(&league.teams, league.season, 1).is_empty());
+        assert!(samples_from_teams(&league.teams, league.season - 1, 1).is_empty());
+    }
+}
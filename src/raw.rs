@@ -0,0 +1,85 @@
+//! Responses kept alongside the JSON they were read from, for fields this crate doesn't model yet.
+
+use std::ops::Deref;
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+/// A response read into `T`, along with the JSON ESPN sent for it.
+///
+/// Derefs to `T`, so the modeled fields read as usual, and `get` reaches anything else:
+///
+/// ```
+/// use espn_fantasy_football::{raw::Raw, team::{TeamId, TeamResponse}};
+///
+/// let json = include_str!("../tests/fixtures/espn/ppr/2023/mTeam.json");
+/// let response: Raw<TeamResponse> = serde_json::from_str(json).unwrap();
+/// assert_eq!(response.teams.len(), 4);
+/// let team = response.team(TeamId(1)).unwrap();
+/// let waiver_rank: u8 = team.get("/waiverRank").unwrap();
+/// assert!(waiver_rank > 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Raw<T> {
+    value: T,
+    json: Value,
+}
+
+impl<T: DeserializeOwned> Raw<T> {
+    pub fn from_json(json: Value) -> Result<Raw<T>, serde_json::Error> {
+        let value = T::deserialize(&json)?;
+        Ok(Raw { value, json })
+    }
+
+    /// The part of the JSON at `pointer`, such as `/teams/0`, read into `U` and kept with its JSON.
+    pub fn at<U: DeserializeOwned>(&self, pointer: &str) -> Option<Raw<U>> {
+        Raw::from_json(self.json.pointer(pointer)?.clone()).ok()
+    }
+}
+
+impl<T> Raw<T> {
+    /// The JSON ESPN sent.
+    pub fn json(&self) -> &Value {
+        &self.json
+    }
+
+    /// The JSON at `pointer`, such as `/status/isExpired`.
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        self.json.pointer(pointer)
+    }
+
+    /// The JSON at `pointer` read into `U`, or `None` if it isn't there or isn't a `U`.
+    pub fn get<U: DeserializeOwned>(&self, pointer: &str) -> Option<U> {
+        U::deserialize(self.json.pointer(pointer)?).ok()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub fn into_parts(self) -> (T, Value) {
+        (self.value, self.json)
+    }
+}
+
+impl<T> Deref for Raw<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Raw<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = Value::deserialize(deserializer)?;
+        Raw::from_json(json).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes the JSON as ESPN sent it, so nothing is lost on a round trip.
+impl<T> Serialize for Raw<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.json.serialize(serializer)
+    }
+}
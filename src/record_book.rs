@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    history::{GameResult, LeagueHistory, SeasonSnapshot},
+    matchup::MatchupKind,
+    members::MemberId,
+    team::TeamId,
+};
+
+/// A single team score for one scoring period.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WeeklyScore {
+    pub member: MemberId,
+    pub team: TeamId,
+    pub season: u16,
+    pub scoring_period_id: u8,
+    pub points: f32,
+}
+
+/// A run of consecutive wins, which may carry over from one season into the next.
+///
+/// Unlike the `streak_length` on a team's `Record`, which is the streak a team is on within one
+/// season, this is counted from the games themselves across seasons, and consolation games don't
+/// extend or end it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Streak {
+    pub member: MemberId,
+    /// The season and matchup period of the first win.
+    pub start: (u16, u8),
+    /// The season and matchup period of the last win.
+    pub end: (u16, u8),
+    pub length: u16,
+}
+
+/// A team's regular season points total.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SeasonTotal {
+    pub member: MemberId,
+    pub team: TeamId,
+    pub season: u16,
+    pub points: f32,
+}
+
+/// The all-time records of the league.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct RecordBook {
+    pub highest_score: Option<WeeklyScore>,
+    pub lowest_score: Option<WeeklyScore>,
+    pub biggest_margin: Option<GameResult>,
+    pub longest_win_streak: Option<Streak>,
+    pub most_points_in_season: Option<SeasonTotal>,
+    /// The seasons each member won the league.
+    pub championships: HashMap<MemberId, Vec<u16>>,
+}
+
+impl RecordBook {
+    pub fn from_history(history: &LeagueHistory) -> RecordBook {
+        let mut book = RecordBook::default();
+        for season in &history.seasons {
+            for score in weekly_scores(season) {
+                if book
+                    .highest_score
+                    .as_ref()
+                    .is_none_or(|h| score.points > h.points)
+                {
+                    book.highest_score = Some(score.clone());
+                }
+                if book
+                    .lowest_score
+                    .as_ref()
+                    .is_none_or(|l| score.points < l.points)
+                {
+                    book.lowest_score = Some(score);
+                }
+            }
+            for game in &season.games() {
+                if book
+                    .biggest_margin
+                    .as_ref()
+                    .is_none_or(|b| game.margin() > b.margin())
+                {
+                    book.biggest_margin = Some(game.clone());
+                }
+            }
+            for team in &season.teams {
+                let member = match season.owner_of(&team.id) {
+                    Some(m) => m.clone(),
+                    None => continue,
+                };
+                let points = team.record.overall.points_for;
+                if book
+                    .most_points_in_season
+                    .as_ref()
+                    .is_none_or(|m| points > m.points)
+                {
+                    book.most_points_in_season = Some(SeasonTotal {
+                        member,
+                        team: team.id,
+                        season: season.season,
+                        points,
+                    });
+                }
+            }
+            if let Some(champion) = champion(season) {
+                book.championships
+                    .entry(champion.clone())
+                    .or_default()
+                    .push(season.season);
+            }
+        }
+        for streak in win_streaks(&history.games()) {
+            if book
+                .longest_win_streak
+                .as_ref()
+                .is_none_or(|l| streak.length > l.length)
+            {
+                book.longest_win_streak = Some(streak);
+            }
+        }
+        book
+    }
+
+    /// Members ordered by number of championships, most first.
+    pub fn championship_leaders(&self) -> Vec<(&MemberId, usize)> {
+        let mut leaders = self
+            .championships
+            .iter()
+            .map(|(member, seasons)| (member, seasons.len()))
+            .collect::<Vec<_>>();
+        leaders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0 .0.cmp(&b.0 .0)));
+        leaders
+    }
+}
+
+impl LeagueHistory {
+    pub fn record_book(&self) -> RecordBook {
+        RecordBook::from_history(self)
+    }
+}
+
+/// Every team score of the season, split by scoring period where ESPN provides the breakdown.
+///
+/// Without the breakdown, a matchup's total only counts when the matchup period is a single
+/// scoring period, since a two-week total isn't a weekly score.
+fn weekly_scores(season: &SeasonSnapshot) -> Vec<WeeklyScore> {
+    let mut scores = Vec::new();
+    for matchup in season.matchups.iter().filter(|m| m.winner.is_decided()) {
+        let single_period = match season
+            .settings
+            .schedule_settings
+            .matchup_periods
+            .get(&matchup.matchup_period_id)
+        {
+            Some(periods) if periods.len() == 1 => Some(periods[0]),
+            _ => None,
+        };
+        for side in matchup.teams() {
+            let member = match season.owner_of(&side.team_id) {
+                Some(m) => m.clone(),
+                None => continue,
+            };
+            match &side.points_by_scoring_period {
+                Some(periods) if !periods.is_empty() => {
+                    for (period, points) in periods {
+                        scores.push(WeeklyScore {
+                            member: member.clone(),
+                            team: side.team_id,
+                            season: season.season,
+                            scoring_period_id: *period as u8,
+                            points: *points,
+                        })
+                    }
+                }
+                _ => {
+                    if let Some(scoring_period_id) = single_period {
+                        scores.push(WeeklyScore {
+                            member,
+                            team: side.team_id,
+                            season: season.season,
+                            scoring_period_id,
+                            points: side.total_points,
+                        })
+                    }
+                }
+            }
+        }
+    }
+    scores
+}
+
+/// The winner of the season's final, the last winners bracket matchup. `None` until it's decided.
+fn champion(season: &SeasonSnapshot) -> Option<&MemberId> {
+    let last = season
+        .matchups
+        .iter()
+        .filter(|m| m.kind() == MatchupKind::Playoff)
+        .max_by_key(|m| m.matchup_period_id)?;
+    season.owner_of(&last.winner_team()?)
+}
+
+/// The longest win streak each member put together, counted across seasons in schedule order.
+/// A loss or tie ends a streak. Consolation games are skipped.
+fn win_streaks(games: &[GameResult]) -> Vec<Streak> {
+    let mut ordered = games
+        .iter()
+        .filter(|g| g.kind != MatchupKind::Consolation)
+        .collect::<Vec<_>>();
+    ordered.sort_by_key(|g| (g.season, g.matchup_period_id));
+    let mut longest: HashMap<MemberId, Streak> = HashMap::new();
+    let mut current: HashMap<MemberId, Streak> = HashMap::new();
+    for game in ordered {
+        let at = (game.season, game.matchup_period_id);
+        for side in [&game.home, &game.away] {
+            if !matches!(game.winner(), Some(w) if w.member == side.member) {
+                current.remove(&side.member);
+                continue;
+            }
+            let run = current
+                .entry(side.member.clone())
+                .or_insert_with(|| Streak {
+                    member: side.member.clone(),
+                    start: at,
+                    end: at,
+                    length: 0,
+                });
+            run.end = at;
+            run.length += 1;
+            if longest
+                .get(&side.member)
+                .is_none_or(|l| run.length > l.length)
+            {
+                longest.insert(side.member.clone(), run.clone());
+            }
+        }
+    }
+    let mut streaks = longest.into_values().collect::<Vec<_>>();
+    streaks.sort_by(|a, b| a.member.0.cmp(&b.member.0));
+    streaks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{history::MemberScore, synthetic::LeagueGenerator};
+
+    fn game(season: u16, period: u8, winner: &str, loser: &str) -> GameResult {
+        game_of_kind(season, period, winner, loser, MatchupKind::Regular)
+    }
+
+    fn game_of_kind(
+        season: u16,
+        period: u8,
+        winner: &str,
+        loser: &str,
+        kind: MatchupKind,
+    ) -> GameResult {
+        let side = |member: &str, points: f32| MemberScore {
+            member: MemberId(member.to_string()),
+            team: TeamId(1),
+            points,
+        };
+        GameResult {
+            season,
+            matchup_period_id: period,
+            kind,
+            home: side(winner, 100.0),
+            away: side(loser, 90.0),
+        }
+    }
+
+    #[test]
+    fn win_streaks_carry_across_seasons() {
+        let games = vec![
+            game(2023, 2, "A", "B"),
+            game(2022, 13, "B", "A"),
+            game(2022, 14, "A", "B"),
+            game(2023, 1, "A", "C"),
+            game(2023, 3, "C", "A"),
+            game(2022, 12, "B", "C"),
+            // A consolation loss doesn't break A's streak.
+            game_of_kind(2022, 15, "B", "A", MatchupKind::Consolation),
+        ];
+        let streaks = win_streaks(&games);
+        let a = streaks.iter().find(|s| s.member.0 == "A").unwrap();
+        assert_eq!(a.length, 3);
+        assert_eq!(a.start, (2022, 14));
+        assert_eq!(a.end, (2023, 2));
+        let b = streaks.iter().find(|s| s.member.0 == "B").unwrap();
+        assert_eq!(b.length, 2);
+        assert!(!streaks.iter().any(|s| s.member.0 == "C" && s.length > 1));
+    }
+
+    #[test]
+    fn champion_is_the_winner_of_the_final() {
+        let league = LeagueGenerator::new(11).generate();
+        let season = SeasonSnapshot {
+            season: league.season,
+            settings: league.settings,
+            teams: league.teams,
+            members: league.members,
+            matchups: league.schedule,
+        };
+        let last_period = season.matchups.iter().map(|m| m.matchup_period_id).max();
+        let finals = season
+            .matchups
+            .iter()
+            .filter(|m| m.kind() == MatchupKind::Playoff)
+            .filter(|m| Some(m.matchup_period_id) == last_period)
+            .collect::<Vec<_>>();
+        assert_eq!(finals.len(), 1);
+        let champion = season
+            .owner_of(&finals[0].winner_team().unwrap())
+            .unwrap()
+            .clone();
+
+        let history = LeagueHistory::new(vec![season]);
+        let book = history.record_book();
+        assert_eq!(book.championships.len(), 1);
+        assert_eq!(book.championships[&champion], vec![league.season]);
+        assert_eq!(book.championship_leaders()[0], (&champion, 1));
+        let highest = book.highest_score.unwrap();
+        assert!(highest.points >= book.lowest_score.unwrap().points);
+    }
+
+    #[test]
+    fn weekly_scores_skip_totals_spanning_several_periods() {
+        let league = LeagueGenerator::new(5).generate();
+        let mut season = SeasonSnapshot {
+            season: league.season,
+            settings: league.settings,
+            teams: league.teams,
+            members: league.members,
+            matchups: league.schedule,
+        };
+        for matchup in &mut season.matchups {
+            for side in matchup.home.iter_mut().chain(matchup.away.iter_mut()) {
+                side.points_by_scoring_period = None;
+            }
+        }
+        let last = season
+            .matchups
+            .iter()
+            .map(|m| m.matchup_period_id)
+            .max()
+            .unwrap();
+        let sides_in = |season: &SeasonSnapshot, last_only: bool| {
+            season
+                .matchups
+                .iter()
+                .filter(|m| m.winner.is_decided())
+                .filter(|m| !last_only || m.matchup_period_id == last)
+                .map(|m| m.teams().count())
+                .sum::<usize>()
+        };
+        let all = sides_in(&season, false);
+        assert!(all > 0);
+        assert_eq!(weekly_scores(&season).len(), all);
+
+        // Make the final a two week matchup.
+        season
+            .settings
+            .schedule_settings
+            .matchup_periods
+            .insert(last, vec![last, last + 1]);
+        let scores = weekly_scores(&season);
+        assert_eq!(scores.len(), all - sides_in(&season, true));
+        assert!(scores.iter().all(|s| s.scoring_period_id != last));
+    }
+}
@@ -0,0 +1,436 @@
+//! Names for stats, NFL teams and positions that ESPN adds after this crate was released.
+//!
+//! Registered stats and NFL teams override the built-in maps in `id_maps`. Positions can only
+//! name ids the crate doesn't know, which `Position::from` leaves as `Position::Other`; their
+//! abbreviations are used when displaying and parsing positions. Lineup slots can't be registered.
+//!
+//! Names are interned for the rest of the program so lookups can return `&'static str`.
+//! Registering the same name again reuses the interned copy.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::{Mutex, OnceLock, RwLock},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    id_maps::{ProTeamId, Sport, StatCategory, StatId, StatIdentifiers, StatUnit, TeamIdentifiers},
+    position::Position,
+};
+
+/// A stat to register.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct StatDefinition {
+    pub name: String,
+    pub field_name: String,
+    #[serde(default)]
+    pub category: StatCategory,
+    #[serde(default)]
+    pub unit: StatUnit,
+    #[serde(default)]
+    pub derived: bool,
+}
+
+/// An NFL team to register.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TeamDefinition {
+    pub name: String,
+    pub abbreviation: String,
+}
+
+/// The contents of a registry file, keyed by ESPN id.
+///
+/// ```toml
+/// [stats.230]
+/// name = "Rushing Attempts Inside the 5"
+/// field_name = "rushing_attempts_inside_5"
+/// category = "Rushing"
+///
+/// [pro_teams.35]
+/// name = "Las Vegas Raiders"
+/// abbreviation = "LV"
+///
+/// [positions]
+/// 6 = "OL"
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct RegistryFile {
+    #[serde(default)]
+    pub stats: HashMap<String, StatDefinition>,
+    #[serde(default)]
+    pub pro_teams: HashMap<String, TeamDefinition>,
+    #[serde(default)]
+    pub positions: HashMap<String, String>,
+}
+
+/// A set of registered names.
+///
+/// `StatId`, `ProTeamId` and `Position` consult the installed registry, which the `register*`
+/// and `load*` functions add to. Build a `Registry` of your own and `install` it to swap every
+/// name at once.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    stats: HashMap<u64, &'static StatIdentifiers>,
+    pro_teams: HashMap<i8, &'static TeamIdentifiers>,
+    positions: HashMap<u64, &'static str>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    pub fn register_stat(&mut self, id: StatId, stat: StatDefinition) {
+        let identifiers = StatIdentifiers::new(
+            intern_str(stat.name),
+            intern_str(stat.field_name),
+            stat.category,
+            stat.unit,
+            stat.derived,
+        );
+        self.stats
+            .insert(id.0, intern(&interned().stats, identifiers));
+    }
+
+    pub fn register_pro_team(&mut self, id: ProTeamId, team: TeamDefinition) {
+        let identifiers =
+            TeamIdentifiers::new(intern_str(team.name), intern_str(team.abbreviation));
+        self.pro_teams
+            .insert(id.0, intern(&interned().pro_teams, identifiers));
+    }
+
+    /// Names a position id this crate doesn't know about. Built-in positions can't be renamed.
+    pub fn register_position(&mut self, id: u64, abbreviation: String) -> Result<(), String> {
+        if let p @ Position::Other(_) = Position::from(id) {
+            self.positions.insert(p.id(), intern_str(abbreviation));
+            Ok(())
+        } else {
+            Err(format!("Position {} is built in and can't be renamed", id))
+        }
+    }
+
+    /// Registers everything in the file, or nothing if any entry is invalid.
+    pub fn register(&mut self, file: RegistryFile) -> Result<(), String> {
+        let mut updated = self.clone();
+        for (id, stat) in file.stats {
+            updated.register_stat(StatId(parse_id(&id)?), stat);
+        }
+        for (id, team) in file.pro_teams {
+            updated.register_pro_team(ProTeamId(parse_id(&id)?), team);
+        }
+        for (id, abbreviation) in file.positions {
+            updated.register_position(parse_id(&id)?, abbreviation)?;
+        }
+        *self = updated;
+        Ok(())
+    }
+
+    /// A registered stat, falling back to the built-in catalog.
+    pub fn stat(&self, id: &StatId) -> Option<&'static StatIdentifiers> {
+        self.stats
+            .get(&id.0)
+            .copied()
+            .or_else(|| Sport::Football.stat_catalog().get(id))
+    }
+
+    /// Every known stat, registered or built in, ordered by id.
+    pub fn stats(&self) -> Vec<(StatId, &'static StatIdentifiers)> {
+        let mut stats = Sport::Football
+            .stat_catalog()
+            .iter()
+            .into_iter()
+            .filter(|(id, _)| !self.stats.contains_key(&id.0))
+            .collect::<Vec<_>>();
+        stats.extend(self.stats.iter().map(|(id, s)| (StatId(*id), *s)));
+        stats.sort_by_key(|(id, _)| id.0);
+        stats
+    }
+
+    /// A registered NFL team. The built-in map is consulted by `ProTeamId` itself.
+    pub fn pro_team(&self, id: &ProTeamId) -> Option<&'static TeamIdentifiers> {
+        self.pro_teams.get(&id.0).copied()
+    }
+
+    pub fn position(&self, id: u64) -> Option<&'static str> {
+        self.positions.get(&id).copied()
+    }
+
+    /// The registered position with the abbreviation, ignoring case.
+    pub fn position_by_abbreviation(&self, abbreviation: &str) -> Option<Position> {
+        self.positions
+            .iter()
+            .filter(|(_, a)| a.eq_ignore_ascii_case(abbreviation))
+            .map(|(id, _)| Position::Other(*id))
+            .min_by_key(|p| p.id())
+    }
+}
+
+fn installed() -> &'static RwLock<Registry> {
+    static INSTALLED: OnceLock<RwLock<Registry>> = OnceLock::new();
+    INSTALLED.get_or_init(Default::default)
+}
+
+/// A copy of the installed registry.
+pub fn current() -> Registry {
+    installed().read().expect("registry lock poisoned").clone()
+}
+
+/// Replaces the installed registry, returning the one it replaced.
+pub fn install(registry: Registry) -> Registry {
+    std::mem::replace(
+        &mut *installed().write().expect("registry lock poisoned"),
+        registry,
+    )
+}
+
+#[derive(Default)]
+struct Interned {
+    strings: Mutex<HashSet<&'static str>>,
+    stats: Mutex<Vec<&'static StatIdentifiers>>,
+    pro_teams: Mutex<Vec<&'static TeamIdentifiers>>,
+}
+
+fn interned() -> &'static Interned {
+    static INTERNED: OnceLock<Interned> = OnceLock::new();
+    INTERNED.get_or_init(Default::default)
+}
+
+fn intern_str(value: String) -> &'static str {
+    let mut strings = interned().strings.lock().expect("registry lock poisoned");
+    match strings.get(value.as_str()) {
+        Some(s) => s,
+        None => {
+            let s = Box::leak(value.into_boxed_str());
+            strings.insert(s);
+            s
+        }
+    }
+}
+
+fn intern<T: PartialEq>(pool: &Mutex<Vec<&'static T>>, value: T) -> &'static T {
+    let mut pool = pool.lock().expect("registry lock poisoned");
+    match pool.iter().find(|v| ***v == value) {
+        Some(v) => v,
+        None => {
+            let v = Box::leak(Box::new(value));
+            pool.push(v);
+            v
+        }
+    }
+}
+
+pub fn register_stat(id: StatId, stat: StatDefinition) {
+    installed()
+        .write()
+        .expect("registry lock poisoned")
+        .register_stat(id, stat)
+}
+
+pub fn register_pro_team(id: ProTeamId, team: TeamDefinition) {
+    installed()
+        .write()
+        .expect("registry lock poisoned")
+        .register_pro_team(id, team)
+}
+
+/// Names a position id this crate doesn't know about. Built-in positions can't be renamed.
+pub fn register_position(id: u64, abbreviation: String) -> Result<(), String> {
+    installed()
+        .write()
+        .expect("registry lock poisoned")
+        .register_position(id, abbreviation)
+}
+
+/// Registers everything in the file, or nothing if any entry is invalid.
+pub fn register(file: RegistryFile) -> Result<(), String> {
+    installed()
+        .write()
+        .expect("registry lock poisoned")
+        .register(file)
+}
+
+pub fn load_toml(contents: &str) -> Result<(), String> {
+    register(toml::from_str(contents).map_err(|e| e.to_string())?)
+}
+
+pub fn load_json(contents: &str) -> Result<(), String> {
+    register(serde_json::from_str(contents).map_err(|e| e.to_string())?)
+}
+
+/// Loads a `.toml` or `.json` registry file.
+pub fn load_file(path: impl AsRef<Path>) -> Result<(), String> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => load_toml(&contents),
+        Some("json") => load_json(&contents),
+        _ => Err(format!(
+            "Unsupported registry file {}; expected .toml or .json",
+            path.display()
+        )),
+    }
+}
+
+fn parse_id<T: std::str::FromStr>(id: &str) -> Result<T, String> {
+    id.trim()
+        .parse()
+        .map_err(|_| format!("Invalid id in registry file: {}", id))
+}
+
+pub(crate) fn stat(id: &StatId) -> Option<&'static StatIdentifiers> {
+    installed().read().expect("registry lock poisoned").stat(id)
+}
+
+pub(crate) fn stats() -> Vec<(StatId, &'static StatIdentifiers)> {
+    installed().read().expect("registry lock poisoned").stats()
+}
+
+pub(crate) fn pro_team(id: &ProTeamId) -> Option<&'static TeamIdentifiers> {
+    installed()
+        .read()
+        .expect("registry lock poisoned")
+        .pro_team(id)
+}
+
+pub(crate) fn position(id: u64) -> Option<&'static str> {
+    installed()
+        .read()
+        .expect("registry lock poisoned")
+        .position(id)
+}
+
+pub(crate) fn position_by_abbreviation(abbreviation: &str) -> Option<Position> {
+    installed()
+        .read()
+        .expect("registry lock poisoned")
+        .position_by_abbreviation(abbreviation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Puts back the registry that was installed before the test.
+    struct Restore(Option<Registry>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(registry) = self.0.take() {
+                install(registry);
+            }
+        }
+    }
+
+    fn file(toml: &str) -> RegistryFile {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn registered_names_extend_and_override_built_in_maps() {
+        let mut registry = Registry::new();
+        registry
+            .register(file(
+                r#"
+                [stats.900]
+                name = "Test Stat"
+                field_name = "test_stat"
+                category = "Rushing"
+                unit = "Yards"
+
+                [stats.221]
+                name = "Every 50 Field Goal Yards"
+                field_name = "field_goal_made_yards_each_50"
+                category = "Kicking"
+                derived = true
+
+                [pro_teams.90]
+                name = "Test Team"
+                abbreviation = "TST"
+
+                [positions]
+                90 = "OL"
+                "#,
+            ))
+            .unwrap();
+
+        assert_eq!(registry.stat(&StatId(900)).unwrap().name(), "Test Stat");
+        assert_eq!(
+            registry.stat(&StatId(221)).unwrap().name(),
+            "Every 50 Field Goal Yards"
+        );
+        assert_eq!(
+            registry
+                .stats()
+                .iter()
+                .filter(|(id, _)| id.0 == 221)
+                .count(),
+            1
+        );
+        assert_eq!(
+            registry.pro_team(&ProTeamId(90)),
+            Some(&TeamIdentifiers::new("Test Team", "TST"))
+        );
+        assert_eq!(
+            registry.position_by_abbreviation("ol"),
+            Some(Position::Other(90))
+        );
+        // The installed registry is untouched.
+        assert_ne!(StatId(221).to_name(), "Every 50 Field Goal Yards");
+    }
+
+    #[test]
+    fn invalid_files_register_nothing() {
+        let mut registry = Registry::new();
+        let bad_id = file(
+            r#"
+            [stats.901]
+            name = "Fine"
+            field_name = "fine"
+
+            [stats.abc]
+            name = "x"
+            field_name = "x"
+            "#,
+        );
+        assert!(registry.register(bad_id).is_err());
+        assert!(registry.stat(&StatId(901)).is_none());
+
+        let built_in_position =
+            file("[stats.901]\nname = \"Fine\"\nfield_name = \"fine\"\n[positions]\n1 = \"Q\"");
+        assert!(registry.register(built_in_position).is_err());
+        assert!(registry.stat(&StatId(901)).is_none());
+        assert!(registry.position(1).is_none());
+    }
+
+    #[test]
+    fn installed_registry_names_ids() {
+        let _restore = Restore(Some(current()));
+        load_toml(
+            r#"
+            [stats.902]
+            name = "Installed Stat"
+            field_name = "installed_stat"
+            unit = "Yards"
+
+            [pro_teams.91]
+            name = "Installed Team"
+            abbreviation = "INS"
+
+            [positions]
+            91 = "LS"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(StatId(902).to_name(), "Installed Stat");
+        assert_eq!(StatId(902).unit(), StatUnit::Yards);
+        assert_eq!(StatId::from_field_name("installed_stat"), Some(StatId(902)));
+        assert_eq!(ProTeamId(91).to_abbreviation(), "INS");
+        assert_eq!(Position::from(91).abbreviation(), "LS");
+        assert_eq!(Position::from(91).to_string(), "LS");
+        assert_eq!("ls".parse(), Ok(Position::Other(91)));
+        assert!(load_toml("[stats.abc]\nname = \"x\"\nfield_name = \"x\"").is_err());
+    }
+}
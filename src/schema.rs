@@ -0,0 +1,382 @@
+//! Spot where ESPN's responses and this crate's structs have drifted apart.
+//!
+//! A response is deserialized into its struct, serialized back, and the two compared key by key.
+//! Fields only in the response are ones we don't model. Fields only in the struct are required
+//! ones ESPN didn't send; optional fields serialize as `null` when absent, so those are skipped,
+//! which also keeps fields outside the requested view out of the report.
+//!
+//! Serde gives up at the first required field that's missing. To find the rest, each one is
+//! filled with a placeholder of whatever type fits and the response is read again, until it
+//! reads. The placeholders are then missing from the response, so the comparison reports them.
+//!
+//! Capturing unknown fields with a `#[serde(flatten)]` map on each struct would also work, but
+//! flatten buffers every field through serde's private content type first. That breaks the many
+//! maps keyed by integers in ESPN's JSON, and would add a field to every public struct. The round
+//! trip needs nothing from the structs, so it covers them all, nested ones included.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// A field that drifted, with array indexes collapsed to `[]`, and how often it was seen.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct FieldDrift {
+    pub path: String,
+    pub occurrences: usize,
+}
+
+/// How one response compares to the struct it's read into.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct SchemaReport {
+    /// The request the response answered, such as `seasons/2023/segments/0/leagues/1?view=mTeam`.
+    pub view: String,
+    pub type_name: String,
+    /// Fields ESPN sent that the struct doesn't model.
+    pub unknown: Vec<FieldDrift>,
+    /// Required fields ESPN didn't send.
+    pub missing: Vec<FieldDrift>,
+    /// Why the response couldn't be read, if it couldn't. When the only problem is missing
+    /// fields, they and any unknown fields are still reported.
+    pub error: Option<String>,
+}
+
+impl SchemaReport {
+    /// An empty report on reading `view` into `T`.
+    pub fn new<T>(view: impl Into<String>) -> SchemaReport {
+        SchemaReport {
+            view: view.into(),
+            type_name: std::any::type_name::<T>()
+                .rsplit("::")
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the response and struct line up exactly.
+    pub fn is_clean(&self) -> bool {
+        self.unknown.is_empty() && self.missing.is_empty() && self.error.is_none()
+    }
+
+    pub fn is_unknown(&self, path: &str) -> bool {
+        self.unknown.iter().any(|f| f.path == path)
+    }
+
+    pub fn is_missing(&self, path: &str) -> bool {
+        self.missing.iter().any(|f| f.path == path)
+    }
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} as {}", self.view, self.type_name)?;
+        if let Some(error) = &self.error {
+            writeln!(f, "  error: {}", error)?;
+        }
+        for field in &self.unknown {
+            writeln!(f, "  unknown: {} ({})", field.path, field.occurrences)?;
+        }
+        for field in &self.missing {
+            writeln!(f, "  missing: {} ({})", field.path, field.occurrences)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads `json` into `T` and reports how the two differ. `view` labels the report.
+pub fn check<T: DeserializeOwned + Serialize>(
+    view: impl Into<String>,
+    json: &str,
+) -> (Option<T>, SchemaReport) {
+    let mut report = SchemaReport::new::<T>(view);
+    let raw: Value = match serde_json::from_str(json) {
+        Ok(raw) => raw,
+        Err(e) => {
+            report.error = Some(e.to_string());
+            return (None, report);
+        }
+    };
+    let mut patched = raw.clone();
+    // Fields filled so far, for the report if the response still can't be read.
+    let mut filled_fields: BTreeMap<String, usize> = BTreeMap::new();
+    let typed: T = loop {
+        let e = match serde_path_to_error::deserialize(patched.clone()) {
+            Ok(typed) => break typed,
+            Err(e) => e,
+        };
+        let path = collapse_indexes(&e.path().to_string());
+        let message = e.inner().to_string();
+        if report.error.is_none() {
+            report.error = Some(format!("{} at `{}`", message, path));
+        }
+        let field = missing_field(&message).map(|field| join(&path, field));
+        match field.and_then(|field| Some((fill_missing::<T>(&patched, &field)?, field))) {
+            Some(((filled, count), field)) => {
+                patched = filled;
+                if !filled_fields.keys().any(|f| within(&field, f)) {
+                    filled_fields.insert(field, count);
+                }
+            }
+            None => {
+                report.missing = drifts(filled_fields);
+                return (None, report);
+            }
+        }
+    };
+    let reserialized = serde_json::to_value(&typed).unwrap_or(Value::Null);
+    let mut unknown = BTreeMap::new();
+    let mut missing = BTreeMap::new();
+    diff(&raw, &reserialized, "", &mut unknown, &mut missing);
+    report.unknown = drifts(unknown);
+    report.missing = drifts(missing);
+    let typed = report.error.is_none().then_some(typed);
+    (typed, report)
+}
+
+/// `json` with the missing `field` filled in wherever it's absent, and how many times, using the
+/// first placeholder that `T` reads past. A placeholder that only fails on fields inside it, like
+/// `{}` for a struct, counts; those are filled in turn.
+fn fill_missing<T: DeserializeOwned>(json: &Value, field: &str) -> Option<(Value, usize)> {
+    let placeholders = [
+        Value::from(0),
+        Value::from(""),
+        Value::from(false),
+        Value::Object(Default::default()),
+        Value::Array(Vec::new()),
+    ];
+    let tokens = tokens(field);
+    placeholders.iter().find_map(|placeholder| {
+        let mut filled = json.clone();
+        let count = fill(&mut filled, &tokens, placeholder);
+        if count == 0 {
+            return None;
+        }
+        match serde_path_to_error::deserialize::<_, T>(filled.clone()) {
+            Ok(_) => Some((filled, count)),
+            Err(e) => {
+                let path = collapse_indexes(&e.path().to_string());
+                let message = e.inner().to_string();
+                let fits = match missing_field(&message) {
+                    Some(_) => true,
+                    None => !within(&path, field),
+                };
+                fits.then_some((filled, count))
+            }
+        }
+    })
+}
+
+/// A step along a collapsed path: into an object's field, or into every item of an array.
+enum Token<'a> {
+    Key(&'a str),
+    Items,
+}
+
+fn tokens(path: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for segment in path.split('.') {
+        let key = segment.trim_end_matches("[]");
+        if !key.is_empty() {
+            tokens.push(Token::Key(key));
+        }
+        for _ in 0..(segment.len() - key.len()) / 2 {
+            tokens.push(Token::Items);
+        }
+    }
+    tokens
+}
+
+/// Inserts `placeholder` at the end of the path in every object that lacks it, returning how
+/// many were filled.
+fn fill(value: &mut Value, tokens: &[Token], placeholder: &Value) -> usize {
+    match tokens {
+        [] => 0,
+        [Token::Key(key)] => match value {
+            Value::Object(map) if !map.contains_key(*key) => {
+                map.insert(key.to_string(), placeholder.clone());
+                1
+            }
+            _ => 0,
+        },
+        [Token::Key(key), rest @ ..] => value
+            .get_mut(*key)
+            .map_or(0, |v| fill(v, rest, placeholder)),
+        [Token::Items, rest @ ..] => value.as_array_mut().map_or(0, |items| {
+            items.iter_mut().map(|v| fill(v, rest, placeholder)).sum()
+        }),
+    }
+}
+
+/// Whether `path` is `field` or inside it.
+fn within(path: &str, field: &str) -> bool {
+    path.strip_prefix(field)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['))
+}
+
+fn diff(
+    raw: &Value,
+    typed: &Value,
+    path: &str,
+    unknown: &mut BTreeMap<String, usize>,
+    missing: &mut BTreeMap<String, usize>,
+) {
+    match (raw, typed) {
+        (Value::Object(raw), Value::Object(typed)) => {
+            for (key, value) in raw {
+                let field = join(path, key);
+                match typed.get(key) {
+                    Some(typed) => diff(value, typed, &field, unknown, missing),
+                    None => *unknown.entry(field).or_default() += 1,
+                }
+            }
+            let absent = typed
+                .iter()
+                .filter(|(k, v)| !v.is_null() && !raw.contains_key(*k));
+            for (key, _) in absent {
+                *missing.entry(join(path, key)).or_default() += 1;
+            }
+        }
+        (Value::Array(raw), Value::Array(typed)) => {
+            let items = format!("{}[]", path);
+            for (raw, typed) in raw.iter().zip(typed) {
+                diff(raw, typed, &items, unknown, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn drifts(fields: BTreeMap<String, usize>) -> Vec<FieldDrift> {
+    fields
+        .into_iter()
+        .map(|(path, occurrences)| FieldDrift { path, occurrences })
+        .collect()
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() || path == "." {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Turns `teams[3].roster` into `teams[].roster`.
+fn collapse_indexes(path: &str) -> String {
+    let mut collapsed = String::with_capacity(path.len());
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => {
+                in_index = true;
+                collapsed.push(c);
+            }
+            ']' => {
+                in_index = false;
+                collapsed.push(c);
+            }
+            _ if in_index => {}
+            _ => collapsed.push(c),
+        }
+    }
+    collapsed
+}
+
+/// The field named by serde's "missing field `name`" error.
+fn missing_field(message: &str) -> Option<&str> {
+    let rest = message.strip_prefix("missing field `")?;
+    rest.split('`').next()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::team::TeamResponse;
+
+    #[test]
+    fn reports_unknown_and_missing_fields() {
+        let team = r#"{"abbrev": "T1", "currentProjectedRank": 1, "divisionId": 0,
+            "draftDayProjectedRank": 1, "id": 1, "isActive": true, "location": "Team",
+            "name": "Team 1", "nickname": "One", "playoffSeed": 1, "points": 0, "pointsAdjusted": 0,
+            "pointsDelta": 0, "rankCalculatedFinal": 0, "rankFinal": 0, "waiverRank": 3,
+            "record": {"away": $R, "division": $R, "home": $R, "overall": $R},
+            "transactionCounter": {"acquisitionBudgetSpent": 0, "acquisitions": 0, "drops": 0,
+                "matchupAcquisitionTotals": {}, "misc": 0, "moveToActive": 0, "moveToIR": 0,
+                "paid": 0, "teamCharges": 0, "trades": 0}}"#
+            .replace(
+                "$R",
+                r#"{"gamesBack": 0, "losses": 0, "percentage": 0, "pointsAgainst": 0,
+                    "pointsFor": 0, "streakLength": 0, "streakType": "NONE", "ties": 0, "wins": 0}"#,
+            );
+        let json = format!(
+            r#"{{"gameId": 1, "id": 1, "segmentId": 0, "scoringPeriodId": 1, "teams": [{}, {}]}}"#,
+            team, team
+        );
+        let (teams, report) = check::<TeamResponse>("mTeam", &json);
+        assert_eq!(teams.unwrap().teams.len(), 2);
+        assert_eq!(
+            report.unknown,
+            vec![FieldDrift {
+                path: "teams[].waiverRank".to_string(),
+                occurrences: 2
+            }]
+        );
+        // Optional fields like `roster` and `draftDetail` aren't missing when left out.
+        assert!(report.missing.is_empty());
+        assert!(report.error.is_none());
+
+        let (_, report) = check::<TeamResponse>("mTeam", &json.replace(r#""waiverRank": 3,"#, ""));
+        assert!(report.is_clean(), "{}", report);
+
+        let broken = json.replacen(r#""abbrev": "T1","#, "", 1);
+        let (teams, report) = check::<TeamResponse>("mTeam", &broken);
+        assert!(teams.is_none());
+        assert!(report.is_missing("teams[].abbrev"));
+        assert!(report.error.unwrap().contains("teams[]"));
+    }
+
+    #[test]
+    fn reports_every_missing_field() {
+        let team = r#"{"abbrev": "T1", "currentProjectedRank": 1, "divisionId": 0,
+            "draftDayProjectedRank": 1, "id": 1, "isActive": true, "location": "Team",
+            "name": "Team 1", "nickname": "One", "playoffSeed": 1, "points": 0, "pointsAdjusted": 0,
+            "pointsDelta": 0, "rankCalculatedFinal": 0, "rankFinal": 0, "waiverRank": 3,
+            "transactionCounter": {"acquisitionBudgetSpent": 0, "acquisitions": 0, "drops": 0,
+                "matchupAcquisitionTotals": {}, "misc": 0, "moveToActive": 0, "moveToIR": 0,
+                "paid": 0, "teamCharges": 0, "trades": 0}}"#;
+        let json = format!(
+            r#"{{"gameId": 1, "id": 1, "segmentId": 0, "teams": [{}, {}]}}"#,
+            team.replace(r#""location": "Team","#, ""),
+            team.replace(r#""abbrev": "T1","#, "")
+        );
+        let (teams, report) = check::<TeamResponse>("mTeam", &json);
+        assert!(teams.is_none());
+        assert!(report.error.is_some());
+        let missing = report
+            .missing
+            .iter()
+            .map(|f| (f.path.as_str(), f.occurrences))
+            .collect::<Vec<_>>();
+        // Nothing inside the missing `record` is reported on its own.
+        assert_eq!(
+            missing,
+            vec![
+                ("scoringPeriodId", 1),
+                ("teams[].abbrev", 1),
+                ("teams[].location", 1),
+                ("teams[].record", 2),
+            ]
+        );
+        assert!(report.is_unknown("teams[].waiverRank"));
+
+        // The second team can't be read, but the first team's missing field is still reported.
+        let second = json.rfind(r#""id": 1"#).unwrap();
+        let mut unreadable = json.clone();
+        unreadable.replace_range(second..second + 7, r#""id": "two""#);
+        let (_, report) = check::<TeamResponse>("mTeam", &unreadable);
+        assert!(report.is_missing("teams[].location"));
+        assert!(report.error.unwrap().contains("location"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::team::{Team, TeamId};
+
+/// A game left to be played, with each side's projected score.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RemainingGame {
+    pub home: TeamId,
+    pub away: TeamId,
+    pub home_projection: f32,
+    pub away_projection: f32,
+}
+
+/// Monte Carlo simulation of the rest of the regular season.
+///
+/// Each remaining game is scored by drawing both teams' points from a normal distribution
+/// around their projection. Seeding is by wins (ties count half), then points for, then team id;
+/// division winners do not receive special treatment.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PlayoffSimulation {
+    pub playoff_team_count: u8,
+    /// Standard deviation of a team's weekly score around its projection.
+    pub weekly_std_dev: f32,
+    pub iterations: u32,
+    /// Seed for the random number generator, so repeated runs are comparable.
+    pub seed: u64,
+}
+
+impl PlayoffSimulation {
+    pub fn new(playoff_team_count: u8) -> PlayoffSimulation {
+        PlayoffSimulation {
+            playoff_team_count,
+            weekly_std_dev: 25.0,
+            iterations: 10_000,
+            seed: 0,
+        }
+    }
+
+    /// The chance each team makes the playoffs, starting from their current records.
+    pub fn playoff_odds(
+        &self,
+        teams: &[Team],
+        remaining: &[RemainingGame],
+    ) -> HashMap<TeamId, f32> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut made_playoffs: HashMap<TeamId, u32> = teams.iter().map(|t| (t.id, 0)).collect();
+        for _ in 0..self.iterations {
+            let mut standings: HashMap<TeamId, (f32, f32)> = teams
+                .iter()
+                .map(|t| {
+                    let record = &t.record.overall;
+                    (
+                        t.id,
+                        (
+                            record.wins as f32 + record.ties as f32 / 2.0,
+                            record.points_for,
+                        ),
+                    )
+                })
+                .collect();
+            for game in remaining {
+                let home = sample_normal(&mut rng, game.home_projection, self.weekly_std_dev);
+                let away = sample_normal(&mut rng, game.away_projection, self.weekly_std_dev);
+                let (home_wins, away_wins) = if home > away {
+                    (1.0, 0.0)
+                } else if away > home {
+                    (0.0, 1.0)
+                } else {
+                    (0.5, 0.5)
+                };
+                if let Some(entry) = standings.get_mut(&game.home) {
+                    entry.0 += home_wins;
+                    entry.1 += home;
+                }
+                if let Some(entry) = standings.get_mut(&game.away) {
+                    entry.0 += away_wins;
+                    entry.1 += away;
+                }
+            }
+            let mut order = standings.into_iter().collect::<Vec<_>>();
+            order.sort_by(|a, b| {
+                b.1 .0
+                    .total_cmp(&a.1 .0)
+                    .then_with(|| b.1 .1.total_cmp(&a.1 .1))
+                    .then_with(|| a.0 .0.cmp(&b.0 .0))
+            });
+            for (team, _) in order.iter().take(self.playoff_team_count as usize) {
+                *made_playoffs.entry(*team).or_default() += 1;
+            }
+        }
+        made_playoffs
+            .into_iter()
+            .map(|(team, count)| (team, count as f32 / self.iterations.max(1) as f32))
+            .collect()
+    }
+}
+
+/// Draw from a normal distribution using the Box-Muller transform.
+pub(crate) fn sample_normal(rng: &mut StdRng, mean: f32, std_dev: f32) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen();
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos();
+    mean + z * std_dev
+}
+
+/// The standard normal cumulative distribution, using the Abramowitz and Stegun approximation
+/// of the error function.
+pub(crate) fn normal_cdf(z: f32) -> f32 {
+    let x = z.abs() / std::f32::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_6
+            + t * (-0.284_496_7 + t * (1.421_413_8 + t * (-1.453_152 + t * 1.061_405_4))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
@@ -0,0 +1,326 @@
+//! Where `EspnClient` gets its data: ESPN itself, or responses saved to disk.
+
+use std::{fmt, path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::{cookie::Jar, Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use tokio::{sync::Mutex, time::Instant};
+
+pub const ESPN_FF_BASE_URL: &str = "https://fantasy.espn.com/apis/v3/games/ffl";
+
+/// A request to one of ESPN's fantasy football endpoints, relative to the API base URL.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash)]
+pub struct EspnRequest {
+    /// For example `seasons/2023/segments/0/leagues/1234`.
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// The `x-fantasy-filter` header.
+    pub filter: Option<String>,
+}
+
+impl EspnRequest {
+    /// A request for a league's data in a season.
+    pub fn league(season: u16, league_id: i32) -> EspnRequest {
+        EspnRequest {
+            path: format!("seasons/{}/segments/0/leagues/{}", season, league_id),
+            query: Vec::new(),
+            filter: None,
+        }
+    }
+
+    /// A request for data shared by every league in a season, such as the NFL schedule.
+    pub fn season(season: u16) -> EspnRequest {
+        EspnRequest {
+            path: format!("seasons/{}", season),
+            query: Vec::new(),
+            filter: None,
+        }
+    }
+
+    pub fn view(mut self, view: &str) -> EspnRequest {
+        self.query.push(("view".to_string(), view.to_string()));
+        self
+    }
+
+    pub fn scoring_period(mut self, scoring_period_id: u8) -> EspnRequest {
+        self.query
+            .push(("scoringPeriodId".to_string(), scoring_period_id.to_string()));
+        self
+    }
+
+    pub fn filter(mut self, filter: serde_json::Value) -> EspnRequest {
+        self.filter = Some(filter.to_string());
+        self
+    }
+
+    pub fn season_id(&self) -> Option<u16> {
+        let mut segments = self.path.split('/');
+        segments.find(|s| *s == "seasons")?;
+        segments.next()?.parse().ok()
+    }
+
+    pub fn league_id(&self) -> Option<i32> {
+        let mut segments = self.path.split('/');
+        segments.find(|s| *s == "leagues")?;
+        segments.next()?.parse().ok()
+    }
+
+    pub fn views(&self) -> Vec<&str> {
+        self.query
+            .iter()
+            .filter(|(k, _)| k == "view")
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    pub fn scoring_period_id(&self) -> Option<u8> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == "scoringPeriodId")
+            .and_then(|(_, v)| v.parse().ok())
+    }
+}
+
+impl fmt::Display for EspnRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        for (i, (key, value)) in self.query.iter().enumerate() {
+            write!(f, "{}{}={}", if i == 0 { '?' } else { '&' }, key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a request couldn't be answered.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum FetchError {
+    /// The league is private and no valid cookies were supplied.
+    Unauthorized,
+    /// The league or season doesn't exist, or nothing was saved for the request.
+    NotFound,
+    /// Any other HTTP status.
+    Status(u16),
+    /// The request never got a response, or a file couldn't be read.
+    Transport(String),
+    /// The response didn't match the expected shape.
+    Deserialize(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Unauthorized => write!(f, "401"),
+            FetchError::NotFound => write!(f, "404"),
+            FetchError::Status(status) => write!(f, "Unexpected status {}", status),
+            FetchError::Transport(e) => write!(f, "Request failed: {}", e),
+            FetchError::Deserialize(e) => write!(f, "Deserialization failed: {}", e),
+        }
+    }
+}
+
+impl FetchError {
+    /// Whether the same request might succeed if tried again later: rate limiting, server errors
+    /// and dropped connections.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            FetchError::Status(429) | FetchError::Status(500..=599) | FetchError::Transport(_)
+        )
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Lets getters that report errors as strings pass fetch failures on with `?`.
+impl From<FetchError> for String {
+    fn from(e: FetchError) -> String {
+        e.to_string()
+    }
+}
+
+/// Answers ESPN requests with the raw JSON response body.
+#[async_trait]
+pub trait DataSource: Send + Sync {
+    async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError>;
+
+    /// The HTTP source requests eventually go to, if any. Sources that wrap another pass it on.
+    fn http(&self) -> Option<&HttpSource> {
+        None
+    }
+}
+
+/// Fetches from ESPN over HTTP.
+pub struct HttpSource {
+    pub client: Client,
+    pub base_url: String,
+}
+
+impl HttpSource {
+    /// Pass empty strings for `swid` and `espn_s2` for a public league.
+    pub fn new(swid: &str, espn_s2: &str) -> HttpSource {
+        HttpSource::with_base_url(ESPN_FF_BASE_URL, swid, espn_s2)
+    }
+
+    /// Sends requests to another server that speaks ESPN's API, such as a mock.
+    ///
+    /// The cookies are only sent to `base_url`'s host.
+    pub fn with_base_url(base_url: &str, swid: &str, espn_s2: &str) -> HttpSource {
+        let cookie_store = Jar::default();
+        if !swid.is_empty() && !espn_s2.is_empty() {
+            if let Ok(url) = base_url.parse() {
+                // One cookie per call; a cookie string only sets its first cookie.
+                cookie_store.add_cookie_str(&format!("SWID={swid}"), &url);
+                cookie_store.add_cookie_str(&format!("espn_s2={espn_s2}"), &url);
+            }
+        }
+        let client = match Client::builder()
+            .cookie_store(true)
+            .cookie_provider(cookie_store.into())
+            .build()
+        {
+            Ok(c) => c,
+            Err(e) => panic!("Failed to construct client. Aborting. {e}"),
+        };
+        HttpSource {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl DataSource for HttpSource {
+    async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+        let mut req = self
+            .client
+            .get(format!("{}/{}", self.base_url, request.path))
+            .query(&request.query);
+        if let Some(filter) = &request.filter {
+            req = req.header("x-fantasy-filter", filter);
+        }
+        let res = req
+            .send()
+            .await
+            .map_err(|e| FetchError::Transport(e.to_string()))?;
+        match res.status() {
+            StatusCode::OK => res
+                .text()
+                .await
+                .map_err(|e| FetchError::Transport(e.to_string())),
+            StatusCode::UNAUTHORIZED => Err(FetchError::Unauthorized),
+            StatusCode::NOT_FOUND => Err(FetchError::NotFound),
+            status => Err(FetchError::Status(status.as_u16())),
+        }
+    }
+
+    fn http(&self) -> Option<&HttpSource> {
+        Some(self)
+    }
+}
+
+/// Serves responses previously saved to a directory, without touching the network.
+///
+/// Each response lives at `{season}/{views}.json`, or `{season}/{views}_{scoringPeriodId}.json`
+/// for requests for a single scoring period, where `views` is the request's views sorted and
+/// joined with `+`. For example, `get_teams_at_week(2023, 5)` reads
+/// `2023/mRoster+mTeam_5.json`. The `x-fantasy-filter` header is ignored, so a saved free agent
+/// response is served whatever the requested limit.
+pub struct FileSource {
+    pub dir: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(dir: impl Into<PathBuf>) -> FileSource {
+        FileSource { dir: dir.into() }
+    }
+
+    /// Where the response to a request is saved.
+    pub fn path_for(&self, request: &EspnRequest) -> PathBuf {
+        let mut views = request.views();
+        views.sort();
+        let mut name = views.join("+");
+        if let Some(scoring_period_id) = request.scoring_period_id() {
+            name = format!("{}_{}", name, scoring_period_id);
+        }
+        let season = request
+            .season_id()
+            .map(|s| s.to_string())
+            .unwrap_or_default();
+        self.dir.join(season).join(format!("{}.json", name))
+    }
+}
+
+#[async_trait]
+impl DataSource for FileSource {
+    async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+        match tokio::fs::read_to_string(self.path_for(request)).await {
+            Ok(body) => Ok(body),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(FetchError::NotFound),
+            Err(e) => Err(FetchError::Transport(e.to_string())),
+        }
+    }
+}
+
+/// Passes requests through to another source, starting at most one every `interval`.
+pub struct RateLimitedSource<S> {
+    pub inner: S,
+    pub interval: Duration,
+    next_start: Mutex<Instant>,
+}
+
+impl<S: DataSource> RateLimitedSource<S> {
+    pub fn new(inner: S, interval: Duration) -> RateLimitedSource<S> {
+        RateLimitedSource {
+            inner,
+            interval,
+            next_start: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: DataSource> DataSource for RateLimitedSource<S> {
+    async fn fetch(&self, request: &EspnRequest) -> Result<String, FetchError> {
+        let start = {
+            let mut next_start = self.next_start.lock().await;
+            let start = (*next_start).max(Instant::now());
+            *next_start = start + self.interval;
+            start
+        };
+        tokio::time::sleep_until(start).await;
+        self.inner.fetch(request).await
+    }
+
+    fn http(&self) -> Option<&HttpSource> {
+        self.inner.http()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn file_source_paths() {
+        let source = FileSource::new("dumps");
+        let request = EspnRequest::league(2023, 1234)
+            .view("mTeam")
+            .view("mRoster")
+            .scoring_period(5);
+        assert_eq!(request.season_id(), Some(2023));
+        assert_eq!(request.league_id(), Some(1234));
+        assert_eq!(
+            source.path_for(&request),
+            PathBuf::from("dumps/2023/mRoster+mTeam_5.json")
+        );
+        assert_eq!(
+            source.path_for(&EspnRequest::season(2023).view("proTeamSchedules_wl")),
+            PathBuf::from("dumps/2023/proTeamSchedules_wl.json")
+        );
+        assert_eq!(
+            request.to_string(),
+            "seasons/2023/segments/0/leagues/1234?view=mTeam&view=mRoster&scoringPeriodId=5"
+        );
+    }
+}
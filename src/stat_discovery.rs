@@ -0,0 +1,339 @@
+//! Find stat ids ESPN sends that aren't in the catalog yet, and guess what they count.
+//!
+//! Scan rosters, matchups, free agents and player cards into a `StatDiscovery`, then
+//! `unknown_stats` describes how each unnamed stat is distributed and which known stats it moves
+//! with.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    free_agent::FreeAgent,
+    id_maps::StatId,
+    matchup::{Matchup, Roster},
+    player::{Player, PlayerCard, PlayerId, StatSource, StatSplit},
+    position::Position,
+    team::{Team, TeamId},
+};
+
+/// Stats seen alongside an unknown one fewer times than this aren't suggested as its meaning.
+const MIN_SHARED_LINES: usize = 3;
+const MIN_CORRELATION: f32 = 0.5;
+const MAX_SUGGESTIONS: usize = 3;
+
+/// One stat line a player put up, as it was scanned.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct StatLineSample {
+    pub player_id: PlayerId,
+    pub player_name: String,
+    pub position: Position,
+    /// The fantasy team the player was on when scanned, if any.
+    pub team_id: Option<TeamId>,
+    pub season_id: u32,
+    /// Zero for a season-long line.
+    pub scoring_period_id: u8,
+    pub source: StatSource,
+    pub split: StatSplit,
+    pub stats: HashMap<u16, f32>,
+}
+
+/// How a stat's values are spread.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct Distribution {
+    pub count: usize,
+    pub nonzero: usize,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+}
+
+impl Distribution {
+    pub fn from_values(values: impl IntoIterator<Item = f32>) -> Distribution {
+        let mut distribution = Distribution {
+            min: f32::INFINITY,
+            max: f32::NEG_INFINITY,
+            ..Default::default()
+        };
+        let mut total = 0.0;
+        for value in values {
+            distribution.count += 1;
+            if value != 0.0 {
+                distribution.nonzero += 1;
+            }
+            distribution.min = distribution.min.min(value);
+            distribution.max = distribution.max.max(value);
+            total += value as f64;
+        }
+        if distribution.count == 0 {
+            return Distribution::default();
+        }
+        distribution.mean = (total / distribution.count as f64) as f32;
+        distribution
+    }
+}
+
+/// A known stat an unknown one might be related to.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Suggestion {
+    pub stat_id: StatId,
+    /// Pearson correlation across the stat lines both appear in.
+    pub correlation: f32,
+    /// Set when the unknown stat is always this multiple of the known one, e.g. 0.04 for
+    /// "Every 25 Passing Yards" against passing yards.
+    pub ratio: Option<f32>,
+    pub shared_lines: usize,
+}
+
+/// A stat id missing from the catalog, with where it showed up and what it may mean.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct UnknownStat {
+    pub stat_id: StatId,
+    pub overall: Distribution,
+    pub by_position: HashMap<Position, Distribution>,
+    pub by_week: HashMap<u8, Distribution>,
+    /// Most likely first.
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Stat lines scanned so far, each player's line for a season, period and source counted once.
+#[derive(Debug, Default, Clone)]
+pub struct StatDiscovery {
+    lines: Vec<StatLineSample>,
+    seen: HashSet<(PlayerId, u32, u8, u32, u32)>,
+}
+
+impl StatDiscovery {
+    pub fn new() -> StatDiscovery {
+        StatDiscovery::default()
+    }
+
+    pub fn add_player(&mut self, player: &Player, team_id: Option<TeamId>) {
+        for stats in player.stats.iter().flatten() {
+            let key = (
+                player.id,
+                stats.season_id,
+                stats.scoring_period_id,
+                stats.stat_source_id,
+                stats.stat_split_type_id,
+            );
+            if !self.seen.insert(key) {
+                continue;
+            }
+            self.lines.push(StatLineSample {
+                player_id: player.id,
+                player_name: player.full_name.clone(),
+                position: player.default_position_id,
+                team_id,
+                season_id: stats.season_id,
+                scoring_period_id: stats.scoring_period_id,
+                source: stats.source(),
+                split: stats.split(),
+                stats: stats.stats.clone(),
+            });
+        }
+    }
+
+    pub fn add_roster(&mut self, roster: &Roster, team_id: TeamId) {
+        for entry in &roster.entries {
+            self.add_player(&entry.player_pool_entry.player, Some(team_id));
+        }
+    }
+
+    /// The teams must include rosters, as returned by `get_teams_at_week`.
+    pub fn add_teams(&mut self, teams: &[Team]) {
+        for team in teams {
+            if let Some(roster) = &team.roster {
+                self.add_roster(roster, team.id);
+            }
+        }
+    }
+
+    /// The matchups must include rosters, as returned by `get_matchups_for_week`.
+    pub fn add_matchups(&mut self, matchups: &[Matchup]) {
+        for side in matchups.iter().flat_map(|m| m.teams()) {
+            if let Some(roster) = &side.roster_for_current_scoring_period {
+                self.add_roster(roster, side.team_id);
+            }
+        }
+    }
+
+    pub fn add_free_agents(&mut self, players: &[FreeAgent]) {
+        for free_agent in players {
+            self.add_player(&free_agent.player, None);
+        }
+    }
+
+    /// Player cards carry lines from past seasons too, which rosters and free agents don't.
+    pub fn add_player_cards(&mut self, cards: &[PlayerCard]) {
+        for card in cards {
+            let team_id = (card.on_team_id != 0).then_some(TeamId(card.on_team_id));
+            self.add_player(&card.player, team_id);
+        }
+    }
+
+    pub fn lines(&self) -> &[StatLineSample] {
+        &self.lines
+    }
+
+    /// Every line the stat appears in, with its value there.
+    pub fn sightings<'a>(
+        &'a self,
+        stat_id: &'a StatId,
+    ) -> impl Iterator<Item = (&'a StatLineSample, f32)> + 'a {
+        self.lines.iter().filter_map(move |line| {
+            let id = u16::try_from(stat_id.0).ok()?;
+            line.stats.get(&id).map(|value| (line, *value))
+        })
+    }
+
+    /// Every stat id seen that the catalog doesn't name, ordered by id.
+    pub fn unknown_stats(&self) -> Vec<UnknownStat> {
+        let ids: BTreeSet<u16> = self
+            .lines
+            .iter()
+            .flat_map(|l| l.stats.keys())
+            .copied()
+            .collect();
+        let (unknown, known): (Vec<u16>, Vec<u16>) = ids
+            .into_iter()
+            .partition(|id| !StatId(*id as u64).is_known());
+        unknown
+            .into_iter()
+            .map(|id| self.describe(id, &known))
+            .collect()
+    }
+
+    fn describe(&self, id: u16, known: &[u16]) -> UnknownStat {
+        let stat_id = StatId(id as u64);
+        let mut positions: HashMap<Position, Vec<f32>> = HashMap::new();
+        let mut weeks: HashMap<u8, Vec<f32>> = HashMap::new();
+        for (line, value) in self.sightings(&stat_id) {
+            positions.entry(line.position).or_default().push(value);
+            weeks.entry(line.scoring_period_id).or_default().push(value);
+        }
+        let mut suggestions: Vec<Suggestion> = known
+            .iter()
+            .filter_map(|other| self.compare(id, *other))
+            .filter(|s| s.correlation.abs() >= MIN_CORRELATION)
+            .collect();
+        suggestions.sort_by(|a, b| b.correlation.abs().total_cmp(&a.correlation.abs()));
+        suggestions.truncate(MAX_SUGGESTIONS);
+        UnknownStat {
+            overall: Distribution::from_values(self.sightings(&stat_id).map(|(_, v)| v)),
+            by_position: positions
+                .into_iter()
+                .map(|(position, v)| (position, Distribution::from_values(v)))
+                .collect(),
+            by_week: weeks
+                .into_iter()
+                .map(|(week, v)| (week, Distribution::from_values(v)))
+                .collect(),
+            suggestions,
+            stat_id,
+        }
+    }
+
+    fn compare(&self, unknown: u16, known: u16) -> Option<Suggestion> {
+        let pairs: Vec<(f64, f64)> = self
+            .lines
+            .iter()
+            .filter_map(|l| Some((*l.stats.get(&unknown)? as f64, *l.stats.get(&known)? as f64)))
+            .collect();
+        if pairs.len() < MIN_SHARED_LINES {
+            return None;
+        }
+        Some(Suggestion {
+            stat_id: StatId(known as u64),
+            correlation: correlation(&pairs)? as f32,
+            ratio: ratio(&pairs).map(|r| r as f32),
+            shared_lines: pairs.len(),
+        })
+    }
+}
+
+/// `None` when either stat never varies, since then nothing can be said.
+fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        covariance += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some(covariance / (var_x * var_y).sqrt())
+}
+
+fn ratio(pairs: &[(f64, f64)]) -> Option<f64> {
+    let (x, y) = pairs.iter().find(|(_, y)| *y != 0.0)?;
+    let ratio = x / y;
+    pairs
+        .iter()
+        .all(|(x, y)| (x - ratio * y).abs() <= 1e-3 * x.abs().max(1.0))
+        .then_some(ratio)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{player::PlayerCardResponse, team::TeamResponse};
+
+    #[test]
+    fn suggests_the_stat_an_unknown_one_tracks() {
+        let json = include_str!("../tests/fixtures/espn/ppr/2023/mRoster+mTeam_1.json");
+        let mut teams = serde_json::from_str::<TeamResponse>(json).unwrap().teams;
+        let mut lines = 0;
+        for team in &mut teams {
+            let roster = team.roster.as_mut().unwrap();
+            for entry in &mut roster.entries {
+                for stats in entry.player_pool_entry.player.stats.iter_mut().flatten() {
+                    if let Some(yards) = stats.stats.get(&3).copied() {
+                        stats.stats.insert(999, yards / 25.0);
+                        lines += 1;
+                    }
+                }
+            }
+        }
+        let mut discovery = StatDiscovery::new();
+        discovery.add_teams(&teams);
+        discovery.add_teams(&teams);
+        let unknown = discovery.unknown_stats();
+        let stat = unknown.iter().find(|s| s.stat_id == StatId(999)).unwrap();
+        assert_eq!(stat.overall.count, lines);
+        assert_eq!(discovery.sightings(&StatId(999)).count(), lines);
+        let best = &stat.suggestions[0];
+        assert_eq!(best.stat_id, StatId(3));
+        assert!((best.correlation - 1.0).abs() < 1e-4);
+        assert!((best.ratio.unwrap() - 0.04).abs() < 1e-4);
+    }
+
+    #[test]
+    fn counts_each_season_of_a_stat_line() {
+        // Player cards and free agents share a shape.
+        let json = include_str!("../tests/fixtures/espn/ppr/2023/kona_player_info_1.json");
+        let cards = serde_json::from_str::<PlayerCardResponse>(json)
+            .unwrap()
+            .players;
+        let mut last_season = cards.clone();
+        for card in &mut last_season {
+            for stats in card.player.stats.iter_mut().flatten() {
+                stats.season_id -= 1;
+            }
+        }
+        let mut discovery = StatDiscovery::new();
+        discovery.add_player_cards(&cards);
+        let lines = discovery.lines().len();
+        assert!(lines > 0);
+        assert!(discovery.lines().iter().all(|l| l.team_id.is_none()));
+        discovery.add_player_cards(&cards);
+        assert_eq!(discovery.lines().len(), lines);
+        discovery.add_player_cards(&last_season);
+        assert_eq!(discovery.lines().len(), 2 * lines);
+        assert!(discovery.lines().iter().any(|l| l.season_id == 2022));
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    matchup::RosterSlot,
+    player::PlayerId,
+    position::LineupSlot,
+    team::{Team, TeamId},
+};
+
+string_enum! {
+    /// A player's injury designation.
+    pub enum InjuryStatus {
+        Active => "ACTIVE",
+        Probable => "PROBABLE",
+        Questionable => "QUESTIONABLE",
+        Doubtful => "DOUBTFUL",
+        Out => "OUT",
+        InjuryReserve => "INJURY_RESERVE",
+        Suspension => "SUSPENSION",
+    }
+}
+
+impl InjuryStatus {
+    /// Whether the player may still play. Unknown designations are assumed not to rule them out.
+    pub fn is_available(&self) -> bool {
+        !matches!(
+            self,
+            InjuryStatus::Out | InjuryStatus::InjuryReserve | InjuryStatus::Suspension
+        )
+    }
+    /// Whether the player carries any designation other than active.
+    pub fn is_injured(&self) -> bool {
+        !matches!(self, InjuryStatus::Active)
+    }
+}
+
+string_enum! {
+    /// Where a player sits in the league's player pool.
+    pub enum RosterStatus {
+        FreeAgent => "FREEAGENT",
+        Waivers => "WAIVERS",
+        OnTeam => "ONTEAM",
+    }
+}
+
+string_enum! {
+    /// How a team acquired a player.
+    pub enum AcquisitionType {
+        Draft => "DRAFT",
+        Add => "ADD",
+        Trade => "TRADE",
+    }
+}
+
+string_enum! {
+    /// The state of a proposed transaction.
+    pub enum TransactionStatus {
+        Pending => "PENDING",
+        Executed => "EXECUTED",
+        Canceled => "CANCELED",
+        Vetoed => "VETOED",
+    }
+}
+
+/// A rostered player with an injury designation.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InjuredPlayer {
+    pub player_id: PlayerId,
+    pub player_name: String,
+    pub lineup_slot: LineupSlot,
+    pub status: InjuryStatus,
+    pub projected_points: Option<f32>,
+}
+
+impl InjuredPlayer {
+    pub fn is_starting(&self) -> bool {
+        self.lineup_slot.is_starter()
+    }
+}
+
+/// How much of a team's lineup is hurt for a scoring period.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InjuryExposure {
+    pub team: TeamId,
+    pub injured: Vec<InjuredPlayer>,
+    pub by_status: HashMap<InjuryStatus, usize>,
+    /// Starters who are out, on injured reserve or suspended.
+    pub unavailable_starters: usize,
+    /// Projected points of every injured starter.
+    pub projected_points_at_risk: f32,
+}
+
+impl InjuryExposure {
+    /// The team must include its roster, as returned by `get_teams_at_week`.
+    pub fn for_team(team: &Team, season: u16, scoring_period_id: u8) -> InjuryExposure {
+        let injured = team
+            .roster
+            .as_ref()
+            .map(|r| {
+                r.entries
+                    .iter()
+                    .filter_map(|e| injured_player(e, season, scoring_period_id))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let mut by_status = HashMap::new();
+        for player in &injured {
+            *by_status.entry(player.status.clone()).or_default() += 1;
+        }
+        let starters = injured.iter().filter(|p| p.is_starting());
+        InjuryExposure {
+            team: team.id,
+            unavailable_starters: starters
+                .clone()
+                .filter(|p| !p.status.is_available())
+                .count(),
+            projected_points_at_risk: starters.filter_map(|p| p.projected_points).sum(),
+            by_status,
+            injured,
+        }
+    }
+}
+
+fn injured_player(entry: &RosterSlot, season: u16, scoring_period_id: u8) -> Option<InjuredPlayer> {
+    let player = &entry.player_pool_entry.player;
+    let status = entry
+        .injury_status
+        .as_ref()
+        .or(player.injury_status.as_ref())?;
+    if !status.is_injured() {
+        return None;
+    }
+    Some(InjuredPlayer {
+        player_id: player.id,
+        player_name: player.full_name.clone(),
+        lineup_slot: entry.lineup_slot_id,
+        status: status.clone(),
+        projected_points: player.projected_points(season, scoring_period_id),
+    })
+}
+
+/// Injury exposure for every team in the league, most unavailable starters first.
+pub fn injury_report(teams: &[Team], season: u16, scoring_period_id: u8) -> Vec<InjuryExposure> {
+    let mut report = teams
+        .iter()
+        .map(|t| InjuryExposure::for_team(t, season, scoring_period_id))
+        .collect::<Vec<_>>();
+    report.sort_by(|a, b| {
+        b.unavailable_starters.cmp(&a.unavailable_starters).then(
+            b.projected_points_at_risk
+                .total_cmp(&a.projected_points_at_risk),
+        )
+    });
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::player::Player;
+
+    #[test]
+    fn unknown_statuses_fall_back() {
+        let status: InjuryStatus = serde_json::from_str("\"DAY_TO_DAY\"").unwrap();
+        assert_eq!(status, InjuryStatus::Other("DAY_TO_DAY".to_string()));
+        assert!(status.is_available());
+        assert_eq!(serde_json::to_string(&status).unwrap(), "\"DAY_TO_DAY\"");
+        assert_eq!(RosterStatus::from("ONTEAM"), RosterStatus::OnTeam);
+    }
+
+    #[test]
+    fn startable_players() {
+        let mut player = Player {
+            active: true,
+            ..Default::default()
+        };
+        assert!(player.is_startable());
+        player.injury_status = Some(InjuryStatus::Questionable);
+        assert!(player.is_startable());
+        player.injury_status = Some(InjuryStatus::InjuryReserve);
+        assert!(!player.is_startable());
+    }
+}
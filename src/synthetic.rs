@@ -0,0 +1,1123 @@
+//! Fake leagues built from a seed, for testing analytics without a real league.
+
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    id_maps::{ProTeamId, StatId},
+    league::{
+        AcquisitionSettings, Divison, DraftSettings, FinanceSettings, LeagueMember, LeagueSettings,
+        LeagueStatus, MatchupTieRule, PlayerRankType, RosterSettings, ScheduleSettings,
+        ScoringEnhancementType, ScoringItems, ScoringSettings, ScoringType, TradeSettings,
+    },
+    matchup::{
+        CumulativeScore, Matchup, MatchupOutcome, PlayerPoolEntry, PlayoffTierType, Roster,
+        RosterSlot, TeamMatchupPerformance,
+    },
+    members::MemberId,
+    player::{Player, PlayerId, Stats},
+    position::{LineupSlot, Position},
+    simulation::sample_normal,
+    status::{AcquisitionType, InjuryStatus, RosterStatus},
+    team::{Record, RecordLocales, Team, TeamId, TransactionCounter},
+    timestamp::Timestamp,
+};
+
+/// Every team's starting lineup, and the position filling each slot.
+const LINEUP: [(LineupSlot, Position); 9] = [
+    (LineupSlot::Quarterback, Position::Quarterback),
+    (LineupSlot::RunningBack, Position::RunningBack),
+    (LineupSlot::RunningBack, Position::RunningBack),
+    (LineupSlot::WideReceiver, Position::WideReceiver),
+    (LineupSlot::WideReceiver, Position::WideReceiver),
+    (LineupSlot::TightEnd, Position::TightEnd),
+    (
+        LineupSlot::RunningBackWideReceiverTightEnd,
+        Position::WideReceiver,
+    ),
+    (LineupSlot::TeamDefense, Position::TeamDefense),
+    (LineupSlot::Kicker, Position::Kicker),
+];
+
+/// Every team's bench.
+const BENCH: [Position; 6] = [
+    Position::Quarterback,
+    Position::RunningBack,
+    Position::RunningBack,
+    Position::WideReceiver,
+    Position::WideReceiver,
+    Position::TightEnd,
+];
+
+/// NFL teams by `proTeamId`.
+const PRO_TEAMS: [i8; 32] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 33, 34,
+];
+
+const FIRST_NAMES: [&str; 16] = [
+    "Alex", "Blake", "Casey", "Drew", "Emerson", "Frankie", "Jordan", "Kai", "Logan", "Morgan",
+    "Parker", "Quinn", "Riley", "Sam", "Taylor", "Robin",
+];
+
+const LAST_NAMES: [&str; 16] = [
+    "Adams", "Brooks", "Carter", "Diaz", "Ellis", "Foster", "Garcia", "Hayes", "Ibarra", "Jensen",
+    "Kim", "Lopez", "Moore", "Nguyen", "Owens", "Price",
+];
+
+const LOCATIONS: [&str; 12] = [
+    "Lakeside",
+    "Hilltop",
+    "Riverside",
+    "Northgate",
+    "Southport",
+    "Eastwood",
+    "Westfield",
+    "Pinecrest",
+    "Harbor",
+    "Cedar",
+    "Granite",
+    "Maple",
+];
+
+const NICKNAMES: [&str; 12] = [
+    "Wolves", "Comets", "Titans", "Owls", "Rockets", "Bison", "Sharks", "Falcons", "Miners",
+    "Knights", "Storm", "Ravens",
+];
+
+/// The weekly mean and standard deviation of each stat a position records.
+fn stat_model(position: Position) -> &'static [(u16, f32, f32)] {
+    match position {
+        Position::Quarterback => &[
+            (3, 240.0, 60.0),
+            (4, 1.6, 1.0),
+            (20, 0.8, 0.8),
+            (24, 15.0, 12.0),
+        ],
+        Position::RunningBack => &[
+            (24, 60.0, 25.0),
+            (25, 0.5, 0.6),
+            (53, 3.0, 1.8),
+            (42, 22.0, 14.0),
+            (43, 0.15, 0.35),
+            (72, 0.1, 0.3),
+        ],
+        Position::WideReceiver => &[(53, 5.0, 2.2), (42, 60.0, 28.0), (43, 0.4, 0.6)],
+        Position::TightEnd => &[(53, 4.0, 2.0), (42, 40.0, 20.0), (43, 0.3, 0.5)],
+        Position::Kicker => &[(83, 1.7, 1.0), (86, 2.3, 1.2)],
+        Position::TeamDefense => &[
+            (99, 2.3, 1.4),
+            (95, 0.8, 0.8),
+            (96, 0.6, 0.7),
+            (120, 21.0, 8.0),
+        ],
+        _ => &[],
+    }
+}
+
+/// The stat recording which band of points a defense allowed.
+fn points_allowed_tier(points_allowed: f32) -> u16 {
+    match points_allowed as u32 {
+        0 => 89,
+        1..=6 => 90,
+        7..=13 => 91,
+        14..=17 => 92,
+        18..=21 => 121,
+        22..=27 => 122,
+        28..=34 => 123,
+        35..=45 => 124,
+        _ => 125,
+    }
+}
+
+/// Fantasy points per unit of each stat, roughly ESPN's defaults.
+fn scoring_points(rank_type: &PlayerRankType) -> Vec<(u16, f32)> {
+    let mut points = vec![
+        (3, 0.04),
+        (4, 4.0),
+        (20, -2.0),
+        (24, 0.1),
+        (25, 6.0),
+        (42, 0.1),
+        (43, 6.0),
+        (72, -2.0),
+        (83, 3.0),
+        (86, 1.0),
+        (95, 2.0),
+        (96, 2.0),
+        (99, 1.0),
+        (89, 5.0),
+        (90, 4.0),
+        (91, 3.0),
+        (92, 1.0),
+        (121, 0.0),
+        (122, 0.0),
+        (123, -1.0),
+        (124, -3.0),
+        (125, -5.0),
+    ];
+    if rank_type.is_ppr() {
+        points.push((53, 1.0));
+    }
+    points
+}
+
+/// Builds a fake league that is consistent throughout: scores add up from player stat lines,
+/// records and seeds follow from the matchups, and the playoff bracket from the seeds.
+///
+/// Set the public fields to change the shape of the league before calling `generate`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct LeagueGenerator {
+    pub seed: u64,
+    pub season: u16,
+    pub league_id: i32,
+    pub size: u8,
+    /// Category leagues decide matchups by stats won, everything else by points.
+    pub scoring_type: ScoringType,
+    /// Whether receptions score.
+    pub rank_type: PlayerRankType,
+    pub divisions: u8,
+    pub playoff_team_count: u8,
+    pub regular_season_weeks: u8,
+    /// How many weeks have been played. `None` plays the whole season, playoffs included.
+    pub weeks_played: Option<u8>,
+}
+
+impl LeagueGenerator {
+    pub fn new(seed: u64) -> LeagueGenerator {
+        LeagueGenerator {
+            seed,
+            season: 2023,
+            league_id: 1,
+            size: 10,
+            scoring_type: ScoringType::HeadToHeadPoints,
+            rank_type: PlayerRankType::Ppr,
+            divisions: 2,
+            playoff_team_count: 4,
+            regular_season_weeks: 14,
+            weeks_played: None,
+        }
+    }
+
+    /// Rounds of single elimination needed to settle the playoffs.
+    pub fn playoff_weeks(&self) -> u8 {
+        match self.playoff_team_count {
+            0 | 1 => 0,
+            n => (n as u32).next_power_of_two().trailing_zeros() as u8,
+        }
+    }
+
+    /// Every week of the season, playoffs included. Each lasts one scoring period.
+    pub fn total_weeks(&self) -> u8 {
+        self.regular_season_weeks + self.playoff_weeks()
+    }
+
+    pub fn generate(&self) -> SyntheticLeague {
+        assert!(self.size >= 2, "A league needs at least two teams");
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let total_weeks = self.total_weeks();
+        let played = self.weeks_played.unwrap_or(total_weeks).min(total_weeks);
+        let scoring = scoring_points(&self.rank_type);
+        let members = self.members(&mut rng);
+        let mut next_player_id = 1_000_000;
+        let rosters: Vec<Vec<(LineupSlot, Player)>> = (0..self.size)
+            .map(|_| {
+                LINEUP
+                    .iter()
+                    .copied()
+                    .chain(BENCH.iter().map(|p| (LineupSlot::Bench, *p)))
+                    .map(|(slot, position)| {
+                        next_player_id += 1;
+                        let player = self.player(
+                            &mut rng,
+                            PlayerId(next_player_id),
+                            position,
+                            played,
+                            &scoring,
+                        );
+                        (slot, player)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut schedule = Vec::new();
+        let mut standings = Standings::new(self.size, self.divisions);
+        let pairings = round_robin(self.size);
+        for week in 1..=self.regular_season_weeks {
+            for (home, away) in &pairings[(week as usize - 1) % pairings.len()] {
+                let id = schedule.len() as u16 + 1;
+                let matchup = self.matchup(id, week, *home, *away, &rosters, played, None);
+                if week <= played {
+                    standings.record(&matchup);
+                }
+                schedule.push(matchup);
+            }
+        }
+        standings.finish();
+
+        let seeds = standings.seeds();
+        let mut eliminated: HashMap<TeamId, u8> = HashMap::new();
+        if played >= self.regular_season_weeks && self.playoff_weeks() > 0 {
+            let bracket_size = (self.playoff_team_count as usize).next_power_of_two();
+            let playoff_teams = &seeds[..(self.playoff_team_count as usize).min(seeds.len())];
+            let mut bracket: Vec<Option<TeamId>> = bracket_order(bracket_size)
+                .into_iter()
+                .map(|seed| playoff_teams.get(seed).copied())
+                .collect();
+            for round in 1..=self.playoff_weeks() {
+                let week = self.regular_season_weeks + round;
+                let mut winners = Vec::new();
+                for pair in bracket.chunks(2) {
+                    let (home, away) = match (pair[0], pair[1]) {
+                        (Some(a), Some(b)) if seed_of(&seeds, b) < seed_of(&seeds, a) => {
+                            (Some(b), Some(a))
+                        }
+                        teams => teams,
+                    };
+                    let Some(home) = home else {
+                        winners.push(away);
+                        continue;
+                    };
+                    let id = schedule.len() as u16 + 1;
+                    let matchup = self.matchup(
+                        id,
+                        week,
+                        home,
+                        away,
+                        &rosters,
+                        played,
+                        Some(PlayoffTierType::WinnersBracket),
+                    );
+                    let winner = match (away, &matchup.winner) {
+                        (None, _) => Some(home),
+                        (Some(away), MatchupOutcome::Away) => {
+                            eliminated.insert(home, round);
+                            Some(away)
+                        }
+                        (Some(away), MatchupOutcome::Home | MatchupOutcome::Tie) => {
+                            eliminated.insert(away, round);
+                            Some(home)
+                        }
+                        _ => None,
+                    };
+                    schedule.push(matchup);
+                    winners.push(winner);
+                }
+                if week > played {
+                    break;
+                }
+                bracket = winners;
+            }
+        }
+
+        let complete = played >= total_weeks;
+        let final_ranks: HashMap<TeamId, u8> = if complete {
+            let mut order = seeds.clone();
+            order.sort_by_key(|team| {
+                (
+                    std::cmp::Reverse(eliminated.get(team).copied().unwrap_or(
+                        if seed_of(&seeds, *team) < self.playoff_team_count as usize {
+                            u8::MAX
+                        } else {
+                            0
+                        },
+                    )),
+                    seed_of(&seeds, *team),
+                )
+            });
+            order
+                .into_iter()
+                .enumerate()
+                .map(|(rank, team)| (team, rank as u8 + 1))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let current = (played + 1).min(total_weeks);
+        let teams = (0..self.size)
+            .map(|index| {
+                let id = TeamId(index + 1);
+                let location = LOCATIONS[index as usize % LOCATIONS.len()];
+                let nickname = NICKNAMES[rng.gen_range(0..NICKNAMES.len())];
+                let seed = seed_of(&seeds, id) as u8 + 1;
+                let rank_final = final_ranks.get(&id).copied().unwrap_or_default();
+                let record = standings.records[index as usize].clone();
+                Team {
+                    abbrev: format!("{}{}", &location[..2], &nickname[..1]).to_uppercase(),
+                    current_projected_rank: seed,
+                    division_id: index % self.divisions.max(1),
+                    draft_day_projected_rank: index + 1,
+                    draft_strategy: None,
+                    id,
+                    is_active: true,
+                    location: location.to_string(),
+                    logo: None,
+                    logo_type: None,
+                    name: format!("{} {}", location, nickname),
+                    nickname: nickname.to_string(),
+                    owners: Some(vec![members[index as usize].id.clone()]),
+                    pending_transactions: None,
+                    playoff_seed: seed,
+                    points: record.overall.points_for,
+                    points_adjusted: 0.0,
+                    points_delta: 0.0,
+                    primary_owner: Some(members[index as usize].id.clone()),
+                    rank_calculated_final: rank_final,
+                    rank_final,
+                    record,
+                    roster: Some(roster(&rosters[index as usize], id, None)),
+                    trade_block: None,
+                    transaction_counter: TransactionCounter {
+                        acquisition_budget_spent: 0.0,
+                        acquisitions: 0,
+                        drops: 0,
+                        matchup_acquisition_totals: HashMap::new(),
+                        misc: 0,
+                        move_to_active: 0,
+                        move_to_ir: 0,
+                        paid: 0.0,
+                        team_charges: 0.0,
+                        trades: 0,
+                    },
+                    values_by_stat: None,
+                }
+            })
+            .collect();
+
+        SyntheticLeague {
+            season: self.season,
+            league_id: self.league_id,
+            settings: self.settings(&scoring),
+            status: LeagueStatus {
+                activated_date: None,
+                created_as_league_type: None,
+                creation_info: None,
+                current_league_type: None,
+                current_matchup_period: current as i8,
+                final_scoring_period: Some(total_weeks as i8),
+                first_scoring_period: Some(1),
+                is_active: !complete,
+                is_expired: Some(complete),
+                is_full: Some(true),
+                is_playoff_matchup_edited: None,
+                is_to_be_deleted: None,
+                is_viewable: Some(true),
+                is_waiver_order_edited: None,
+                last_update_info: None,
+                latest_scoring_period: current as i8,
+                previous_seasons: None,
+                standings_update_date: None,
+                teams_joined: Some(self.size as i8),
+                transaction_scoring_period: Some(current as i8),
+                waiver_last_execution_date: None,
+                waiver_process_status: None,
+            },
+            members,
+            teams,
+            schedule,
+        }
+    }
+
+    fn members(&self, rng: &mut StdRng) -> Vec<LeagueMember> {
+        (0..self.size)
+            .map(|index| {
+                let first_name = FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())];
+                let last_name = LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())];
+                LeagueMember {
+                    display_name: format!("{}{}", first_name.to_lowercase(), rng.gen_range(1..100)),
+                    first_name: first_name.to_string(),
+                    id: MemberId(format!(
+                        "{{{:08X}-0000-4000-8000-{:012X}}}",
+                        rng.gen::<u32>(),
+                        index
+                    )),
+                    last_name: last_name.to_string(),
+                    is_league_manager: Some(index == 0),
+                    notification_settings: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    fn player(
+        &self,
+        rng: &mut StdRng,
+        id: PlayerId,
+        position: Position,
+        played: u8,
+        scoring: &[(u16, f32)],
+    ) -> Player {
+        let pro_team = ProTeamId(*PRO_TEAMS.choose(rng).unwrap());
+        let (first_name, last_name) = if position == Position::TeamDefense {
+            let name = pro_team.to_name();
+            let nickname = name.rsplit(' ').next().unwrap_or(name);
+            (nickname.to_string(), "D/ST".to_string())
+        } else {
+            (
+                FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())].to_string(),
+                LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())].to_string(),
+            )
+        };
+        let talent = sample_normal(rng, 1.0, 0.2).clamp(0.5, 1.6);
+        let model = stat_model(position);
+        let mut stats = Vec::new();
+        let mut actual_season = HashMap::new();
+        let mut projected_season = HashMap::new();
+        for week in 1..=self.total_weeks() {
+            let projected: HashMap<u16, f32> = model
+                .iter()
+                .map(|(s, mean, _)| (*s, mean * talent))
+                .collect();
+            let variance = model
+                .iter()
+                .map(|(s, _, sd)| (*s, (sd * talent).powi(2)))
+                .collect();
+            for (stat, value) in &projected {
+                *projected_season.entry(*stat).or_insert(0.0) += value;
+            }
+            stats.push(self.stat_line(
+                1,
+                1,
+                week,
+                &pro_team,
+                with_tier(projected),
+                Some(variance),
+                scoring,
+            ));
+            if week <= played {
+                let actual: HashMap<u16, f32> = model
+                    .iter()
+                    .map(|(s, mean, sd)| {
+                        let value = sample_normal(rng, mean * talent, *sd).round().max(0.0);
+                        (*s, value)
+                    })
+                    .collect();
+                for (stat, value) in &actual {
+                    *actual_season.entry(*stat).or_insert(0.0) += value;
+                }
+                stats.push(self.stat_line(0, 1, week, &pro_team, with_tier(actual), None, scoring));
+            }
+        }
+        for (source, season, weeks) in [
+            (0, actual_season, played),
+            (1, projected_season, self.total_weeks()),
+        ] {
+            let mut line = self.stat_line(source, 0, 0, &pro_team, season, None, scoring);
+            line.applied_average = Some(line.applied_total / weeks.max(1) as f32);
+            stats.push(line);
+        }
+        Player {
+            active: true,
+            default_position_id: position,
+            draft_ranks_by_rank_type: None,
+            droppable: true,
+            eligible_slots: LineupSlot::ALL
+                .iter()
+                .copied()
+                .filter(|s| s.accepts_position(&position))
+                .collect(),
+            first_name: first_name.clone(),
+            full_name: format!("{} {}", first_name, last_name),
+            id,
+            injured: false,
+            injury_status: Some(InjuryStatus::Active),
+            jersey: None,
+            last_name,
+            last_news_date: None,
+            last_video_date: None,
+            outlooks: None,
+            ownership: None,
+            pro_team_id: pro_team,
+            rankings: None,
+            season_outlook: None,
+            stats: Some(stats),
+            universe_id: None,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn stat_line(
+        &self,
+        source: u32,
+        split: u32,
+        scoring_period_id: u8,
+        pro_team: &ProTeamId,
+        stats: HashMap<u16, f32>,
+        variance: Option<HashMap<u16, f32>>,
+        scoring: &[(u16, f32)],
+    ) -> Stats {
+        // Summed in scoring order rather than map order, so totals are the same on every run.
+        let applied: Vec<(StatId, f32)> = scoring
+            .iter()
+            .filter_map(|(stat, points)| {
+                stats
+                    .get(stat)
+                    .map(|value| (StatId(*stat as u64), value * points))
+            })
+            .collect();
+        Stats {
+            applied_average: None,
+            applied_total: applied.iter().map(|(_, points)| points).sum(),
+            applied_stats: Some(applied.into_iter().collect()),
+            external_id: format!("{}{}", self.season, scoring_period_id),
+            id: format!("{}{}{}{}", source, split, self.season, scoring_period_id),
+            pro_team_id: pro_team.clone(),
+            scoring_period_id,
+            season_id: self.season as u32,
+            stat_source_id: source,
+            stat_split_type_id: split,
+            stats,
+            variance,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn matchup(
+        &self,
+        id: u16,
+        week: u8,
+        home: TeamId,
+        away: Option<TeamId>,
+        rosters: &[Vec<(LineupSlot, Player)>],
+        played: u8,
+        playoff_tier_type: Option<PlayoffTierType>,
+    ) -> Matchup {
+        let is_played = week <= played;
+        let performance = |team: TeamId| {
+            let roster = roster(&rosters[team.0 as usize - 1], team, Some(week));
+            let total = starter_points(&roster, self.season, week);
+            TeamMatchupPerformance {
+                cumulative_score: None,
+                games_played: 0,
+                points_by_scoring_period: is_played.then(|| HashMap::from([(week as i8, total)])),
+                roster_for_current_scoring_period: Some(roster),
+                team_id: team,
+                total_points: if is_played { total } else { 0.0 },
+                total_points_live: None,
+                total_projected_points_live: None,
+            }
+        };
+        let mut home = performance(home);
+        let mut away = away.map(performance);
+        let winner = match away.as_mut() {
+            Some(away) if is_played => {
+                if self.scoring_type.is_points_based() || !self.scoring_type.is_head_to_head() {
+                    outcome(home.total_points, away.total_points)
+                } else {
+                    self.category_outcome(&mut home, away, week)
+                }
+            }
+            _ => MatchupOutcome::Undecided,
+        };
+        let winner = match (winner, &playoff_tier_type) {
+            (MatchupOutcome::Tie, Some(_)) => MatchupOutcome::Home,
+            (winner, _) => winner,
+        };
+        Matchup {
+            away,
+            home: Some(home),
+            id,
+            matchup_period_id: week,
+            playoff_tier_type: Some(playoff_tier_type.unwrap_or(PlayoffTierType::None)),
+            winner,
+        }
+    }
+
+    /// Scores a category matchup, one win per scoring stat. Lower is better for stats that
+    /// cost points.
+    fn category_outcome(
+        &self,
+        home: &mut TeamMatchupPerformance,
+        away: &mut TeamMatchupPerformance,
+        week: u8,
+    ) -> MatchupOutcome {
+        let totals = |team: &TeamMatchupPerformance| {
+            let mut totals: HashMap<u16, f32> = HashMap::new();
+            for entry in team
+                .roster_for_current_scoring_period
+                .iter()
+                .flat_map(|r| &r.entries)
+                .filter(|e| e.lineup_slot_id.is_starter())
+            {
+                if let Some(line) = entry.player_pool_entry.player.stat_line(
+                    crate::player::StatSource::Actual,
+                    self.season,
+                    Some(week),
+                ) {
+                    for (stat, value) in &line.stats {
+                        *totals.entry(*stat).or_default() += value;
+                    }
+                }
+            }
+            totals
+        };
+        let (home_totals, away_totals) = (totals(home), totals(away));
+        let (mut home_score, mut away_score) = (
+            CumulativeScore {
+                losses: 0,
+                score_by_stat: None,
+                ties: 0,
+                wins: 0,
+            },
+            CumulativeScore {
+                losses: 0,
+                score_by_stat: None,
+                ties: 0,
+                wins: 0,
+            },
+        );
+        for (stat, points) in scoring_points(&self.rank_type) {
+            let home_value = home_totals.get(&stat).copied().unwrap_or_default();
+            let away_value = away_totals.get(&stat).copied().unwrap_or_default();
+            let (home_value, away_value) = if points < 0.0 {
+                (-home_value, -away_value)
+            } else {
+                (home_value, away_value)
+            };
+            match outcome(home_value, away_value) {
+                MatchupOutcome::Home => {
+                    home_score.wins += 1;
+                    away_score.losses += 1;
+                }
+                MatchupOutcome::Away => {
+                    away_score.wins += 1;
+                    home_score.losses += 1;
+                }
+                _ => {
+                    home_score.ties += 1;
+                    away_score.ties += 1;
+                }
+            }
+        }
+        home.total_points = home_score.wins as f32;
+        away.total_points = away_score.wins as f32;
+        home.cumulative_score = Some(home_score);
+        away.cumulative_score = Some(away_score);
+        outcome(home.total_points, away.total_points)
+    }
+
+    fn settings(&self, scoring: &[(u16, f32)]) -> LeagueSettings {
+        let mut lineup_slot_counts: HashMap<LineupSlot, i8> = HashMap::new();
+        for (slot, _) in LINEUP {
+            *lineup_slot_counts.entry(slot).or_default() += 1;
+        }
+        lineup_slot_counts.insert(LineupSlot::Bench, BENCH.len() as i8);
+        let divisions = self.divisions.max(1);
+        LeagueSettings {
+            acquisition_settings: AcquisitionSettings {
+                acquisition_budget: 100.0,
+                acquisition_limits: -1.0,
+                acquisition_type: "WAIVERS_TRADITIONAL".to_string(),
+                is_using_acquisition_budget: false,
+                matchup_acquisition_limit: -1.0,
+                matchup_limit_per_scoring_period: false,
+                minimum_bid: 0.0,
+                waiver_hours: 24,
+                waiver_order_reset: false,
+                waiver_process_days: vec!["Wednesday".to_string()],
+                waiver_process_hour: 0,
+            },
+            draft_settings: DraftSettings {
+                auction_budget: 200.0,
+                available_date: Timestamp::default(),
+                date: Timestamp::default(),
+                is_trading_enabled: false,
+                keeper_count: 0,
+                keeper_count_future: 0,
+                keeper_order_type: "TRADITIONAL".to_string(),
+                league_sub_type: "NONE".to_string(),
+                order_type: "MANUAL".to_string(),
+                pick_order: (1..=self.size).collect(),
+                time_per_selection: 90,
+                draft_type: "SNAKE".to_string(),
+            },
+            finance_settings: FinanceSettings {
+                entry_fee: 0.0,
+                misc_fee: 0.0,
+                per_loss: 0.0,
+                per_trade: 0.0,
+                player_acquisition: 0.0,
+                player_drop: 0.0,
+                player_move_to_active: 0.0,
+                player_move_to_ir: 0.0,
+            },
+            is_customizable: true,
+            is_public: true,
+            name: format!("Synthetic League {}", self.seed),
+            restriction_type: "NONE".to_string(),
+            roster_settings: RosterSettings {
+                is_bench_unlimited: false,
+                is_using_undroppable_list: false,
+                lineup_locktime_type: "INDIVIDUAL_GAME".to_string(),
+                lineup_slot_counts,
+                lineup_slot_stat_limits: HashMap::new(),
+                move_limit: -1,
+                position_limits: HashMap::new(),
+                roster_locktime_type: "INDIVIDUAL_GAME".to_string(),
+                universe_ids: vec![1],
+            },
+            schedule_settings: ScheduleSettings {
+                divisions: (0..divisions)
+                    .map(|id| Divison {
+                        id: id as u32,
+                        name: format!("Division {}", id + 1),
+                        size: (0..self.size).filter(|t| t % divisions == id).count() as u8,
+                    })
+                    .collect(),
+                matchup_period_count: self.regular_season_weeks,
+                matchup_period_length: 1,
+                matchup_periods: (1..=self.total_weeks()).map(|w| (w, vec![w])).collect(),
+                period_type_id: 1,
+                playoff_matchup_period_length: 1,
+                playoff_seeding_rule: "TOTAL_POINTS_SCORED".to_string(),
+                playoff_seeding_rule_by: 0,
+                playoff_team_count: self.playoff_team_count,
+            },
+            scoring_settings: ScoringSettings {
+                allow_out_of_position_scoring: false,
+                home_team_bonus: 0.0,
+                matchup_tie_rule: MatchupTieRule::None,
+                matchup_tie_rule_by: 0,
+                player_rank_type: self.rank_type.clone(),
+                playoff_home_team_bonus: 0.0,
+                playoff_matchup_tie_rule: MatchupTieRule::HomeTeamWins,
+                playoff_matchup_tie_rule_by: 0,
+                scoring_enhancement_type: ScoringEnhancementType::None,
+                scoring_items: scoring
+                    .iter()
+                    .map(|(stat, points)| ScoringItems {
+                        is_reverse_item: *points < 0.0,
+                        league_ranking: 0.0,
+                        league_total: 0.0,
+                        points: *points,
+                        points_overrides: None,
+                        stat_id: StatId(*stat as u64),
+                    })
+                    .collect(),
+                scoring_type: self.scoring_type.clone(),
+            },
+            size: self.size as i8,
+            trade_settings: TradeSettings {
+                allow_out_of_universe: false,
+                deadline_date: Timestamp::default(),
+                max: -1,
+                revision_hours: 24,
+                veto_votes_required: 4,
+            },
+        }
+    }
+}
+
+/// A fake league, in the same structs the client returns for a real one.
+#[derive(Debug, Serialize)]
+pub struct SyntheticLeague {
+    pub season: u16,
+    pub league_id: i32,
+    pub settings: LeagueSettings,
+    pub status: LeagueStatus,
+    pub members: Vec<LeagueMember>,
+    /// Each team's roster as of the latest scoring period, with every stat line.
+    pub teams: Vec<Team>,
+    /// Every matchup of the season that can be scheduled so far. Playoff rounds appear once the
+    /// teams in them are known.
+    pub schedule: Vec<Matchup>,
+}
+
+impl SyntheticLeague {
+    pub fn matchups_for_week(&self, matchup_period_id: u8) -> Vec<&Matchup> {
+        self.schedule
+            .iter()
+            .filter(|m| m.matchup_period_id == matchup_period_id)
+            .collect()
+    }
+
+    pub fn team(&self, id: TeamId) -> Option<&Team> {
+        self.teams.iter().find(|t| t.id == id)
+    }
+}
+
+/// Adds the points allowed band to a defense's stats.
+fn with_tier(mut stats: HashMap<u16, f32>) -> HashMap<u16, f32> {
+    if let Some(points_allowed) = stats.get(&120).copied() {
+        stats.insert(points_allowed_tier(points_allowed), 1.0);
+    }
+    stats
+}
+
+/// A team's roster, keeping only the stat lines for `week` and the season, or all of them.
+fn roster(players: &[(LineupSlot, Player)], team: TeamId, week: Option<u8>) -> Roster {
+    let entries = players
+        .iter()
+        .map(|(slot, player)| {
+            let mut player = player.clone();
+            if let (Some(week), Some(stats)) = (week, player.stats.as_mut()) {
+                stats.retain(|s| s.scoring_period_id == week || s.scoring_period_id == 0);
+            }
+            RosterSlot {
+                acquisition_date: None,
+                acquisition_type: Some(AcquisitionType::Draft),
+                injury_status: player.injury_status.clone(),
+                lineup_slot_id: *slot,
+                pending_transaction_ids: None,
+                player_id: player.id,
+                player_pool_entry: PlayerPoolEntry {
+                    applied_stat_total: None,
+                    id: player.id,
+                    keeper_value: 0.0,
+                    keeper_value_future: 0.0,
+                    lineup_locked: false,
+                    on_team_id: team,
+                    player,
+                    roster_locked: false,
+                    status: RosterStatus::OnTeam,
+                    trade_locked: false,
+                },
+                status: RosterStatus::OnTeam,
+            }
+        })
+        .collect();
+    Roster {
+        applied_stat_total: None,
+        entries,
+    }
+}
+
+fn starter_points(roster: &Roster, season: u16, week: u8) -> f32 {
+    roster
+        .entries
+        .iter()
+        .filter(|e| e.lineup_slot_id.is_starter())
+        .filter_map(|e| e.player_pool_entry.player.actual_points(season, week))
+        .sum()
+}
+
+fn outcome(home: f32, away: f32) -> MatchupOutcome {
+    if home > away {
+        MatchupOutcome::Home
+    } else if away > home {
+        MatchupOutcome::Away
+    } else {
+        MatchupOutcome::Tie
+    }
+}
+
+/// One round of pairings per week using the circle method, so everyone plays everyone once before
+/// any rematch. With an odd number of teams one team has a bye each week.
+fn round_robin(size: u8) -> Vec<Vec<(TeamId, Option<TeamId>)>> {
+    let mut slots: Vec<Option<TeamId>> = (1..=size).map(|t| Some(TeamId(t))).collect();
+    if slots.len() % 2 == 1 {
+        slots.push(None);
+    }
+    let n = slots.len();
+    (0..n - 1)
+        .map(|round| {
+            let pairs = (0..n / 2)
+                .filter_map(|i| {
+                    let (a, b) = (slots[i], slots[n - 1 - i]);
+                    let (home, away) = if round % 2 == 0 { (a, b) } else { (b, a) };
+                    match (home, away) {
+                        (Some(home), away) => Some((home, away)),
+                        (None, Some(away)) => Some((away, None)),
+                        (None, None) => None,
+                    }
+                })
+                .collect();
+            slots[1..].rotate_right(1);
+            pairs
+        })
+        .collect()
+}
+
+/// Seeds (from zero) in bracket order, so the top seeds meet as late as possible.
+fn bracket_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let len = order.len() * 2;
+        order = order.iter().flat_map(|&s| [s, len - 1 - s]).collect();
+    }
+    order
+}
+
+fn seed_of(seeds: &[TeamId], team: TeamId) -> usize {
+    seeds.iter().position(|t| *t == team).unwrap_or(usize::MAX)
+}
+
+/// Records built up from decided regular season matchups.
+struct Standings {
+    divisions: u8,
+    records: Vec<RecordLocales>,
+}
+
+impl Standings {
+    fn new(size: u8, divisions: u8) -> Standings {
+        let empty = Record {
+            games_back: 0.0,
+            losses: 0,
+            percentage: 0.0,
+            points_against: 0.0,
+            points_for: 0.0,
+            streak_length: 0,
+            streak_type: "NONE".to_string(),
+            ties: 0,
+            wins: 0,
+        };
+        Standings {
+            divisions: divisions.max(1),
+            records: (0..size)
+                .map(|_| RecordLocales {
+                    away: empty.clone(),
+                    division: empty.clone(),
+                    home: empty.clone(),
+                    overall: empty.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn record(&mut self, matchup: &Matchup) {
+        let (Some(home), Some(away)) = (&matchup.home, &matchup.away) else {
+            return;
+        };
+        let same_division =
+            (home.team_id.0 - 1) % self.divisions == (away.team_id.0 - 1) % self.divisions;
+        for (team, opponent, is_home) in [(home, away, true), (away, home, false)] {
+            let result = outcome(team.total_points, opponent.total_points);
+            let records = &mut self.records[team.team_id.0 as usize - 1];
+            let mut locales = vec![&mut records.overall];
+            locales.push(if is_home {
+                &mut records.home
+            } else {
+                &mut records.away
+            });
+            if same_division {
+                locales.push(&mut records.division);
+            }
+            for record in locales {
+                record.points_for += team.total_points;
+                record.points_against += opponent.total_points;
+                let streak_type = match result {
+                    MatchupOutcome::Home => {
+                        record.wins += 1;
+                        "WIN"
+                    }
+                    MatchupOutcome::Away => {
+                        record.losses += 1;
+                        "LOSS"
+                    }
+                    _ => {
+                        record.ties += 1;
+                        "TIE"
+                    }
+                };
+                if record.streak_type == streak_type {
+                    record.streak_length += 1;
+                } else {
+                    record.streak_type = streak_type.to_string();
+                    record.streak_length = 1;
+                }
+            }
+        }
+    }
+
+    /// Fills in win percentages and games back.
+    fn finish(&mut self) {
+        let win_margin = |r: &Record| r.wins as f32 - r.losses as f32;
+        let leader = self
+            .records
+            .iter()
+            .map(|r| win_margin(&r.overall))
+            .fold(f32::MIN, f32::max);
+        for records in &mut self.records {
+            for record in [
+                &mut records.overall,
+                &mut records.home,
+                &mut records.away,
+                &mut records.division,
+            ] {
+                let games = record.wins + record.losses + record.ties;
+                if games > 0 {
+                    record.percentage =
+                        (record.wins as f32 + record.ties as f32 / 2.0) / games as f32;
+                }
+            }
+            records.overall.games_back = (leader - win_margin(&records.overall)) / 2.0;
+        }
+    }
+
+    /// Teams from first seed to last, by winning percentage then points scored.
+    fn seeds(&self) -> Vec<TeamId> {
+        let mut order: Vec<(TeamId, &Record)> = self
+            .records
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (TeamId(i as u8 + 1), &r.overall))
+            .collect();
+        order.sort_by(|(a_id, a), (b_id, b)| {
+            b.percentage
+                .total_cmp(&a.percentage)
+                .then_with(|| b.points_for.total_cmp(&a.points_for))
+                .then_with(|| a_id.0.cmp(&b_id.0))
+        });
+        order.into_iter().map(|(id, _)| id).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generated_leagues_are_consistent() {
+        let league = LeagueGenerator::new(7).generate();
+        let again = LeagueGenerator::new(7).generate();
+        assert_eq!(
+            serde_json::to_value(&league).unwrap(),
+            serde_json::to_value(&again).unwrap()
+        );
+        assert_eq!(league.teams.len(), 10);
+        assert_eq!(league.matchups_for_week(1).len(), 5);
+
+        let wins: u32 = league
+            .teams
+            .iter()
+            .map(|t| t.record.overall.wins as u32)
+            .sum();
+        let losses: u32 = league
+            .teams
+            .iter()
+            .map(|t| t.record.overall.losses as u32)
+            .sum();
+        assert_eq!(wins, losses);
+        for matchup in league.schedule.iter().filter(|m| !m.is_bye()) {
+            for side in matchup.teams() {
+                let roster = side.roster_for_current_scoring_period.as_ref().unwrap();
+                let points = starter_points(roster, league.season, matchup.matchup_period_id);
+                assert!((side.total_points - points).abs() < 1e-3);
+            }
+        }
+
+        // Two playoff rounds, the final between the last two teams standing.
+        let final_week = league.matchups_for_week(16);
+        assert_eq!(final_week.len(), 1);
+        let champion = final_week[0].winner_team().unwrap();
+        assert_eq!(league.team(champion).unwrap().rank_final, 1);
+
+        let partial = LeagueGenerator {
+            weeks_played: Some(5),
+            size: 7,
+            ..LeagueGenerator::new(7)
+        }
+        .generate();
+        assert_eq!(partial.status.current_matchup_period, 6);
+        assert!(partial.matchups_for_week(1).iter().any(|m| m.is_bye()));
+        assert!(partial
+            .matchups_for_week(6)
+            .iter()
+            .all(|m| m.winner == MatchupOutcome::Undecided));
+        assert!(partial.matchups_for_week(15).is_empty());
+    }
+}
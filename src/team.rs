@@ -2,7 +2,10 @@ use std::{collections::HashMap, fmt};
 
 use serde::{Deserialize, Serialize};
 
-use super::{id_maps::StatId, league::DraftDetail, members::MemberId, player::PlayerId};
+use super::{
+    id_maps::StatId, league::DraftDetail, matchup::Roster, members::MemberId, player::PlayerId,
+    raw::Raw, status::TransactionStatus, timestamp::Timestamp,
+};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct TeamResponse {
@@ -17,6 +20,14 @@ pub struct TeamResponse {
     pub scoring_period_id: i8,
     pub teams: Vec<Team>,
 }
+
+impl Raw<TeamResponse> {
+    /// A team along with the JSON ESPN sent for it.
+    pub fn team(&self, id: TeamId) -> Option<Raw<Team>> {
+        let index = self.teams.iter().position(|t| t.id == id)?;
+        self.at(&format!("/teams/{}", index))
+    }
+}
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Team {
     pub abbrev: String,
@@ -54,6 +65,8 @@ pub struct Team {
     #[serde(rename = "rankFinal")]
     pub rank_final: u8,
     pub record: RecordLocales,
+    /// Only present when requested with the `mRoster` view.
+    pub roster: Option<Roster>,
     #[serde(rename = "tradeBlock")]
     pub trade_block: Option<TradeBlock>,
     #[serde(rename = "transactionCounter")]
@@ -71,13 +84,13 @@ pub struct TradeBlock {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PendingTransaction {
     #[serde(rename = "acceptedDate")]
-    pub accepted_date: Option<u128>,
+    pub accepted_date: Option<Timestamp>,
     #[serde(rename = "bidAmount")]
     pub bid_amount: i32,
     #[serde(rename = "executionType")]
     pub execution_type: String,
     #[serde(rename = "expirationDate")]
-    pub expiration_date: Option<u128>,
+    pub expiration_date: Option<Timestamp>,
     pub id: String,
     #[serde(rename = "isActingAsTeamOwner")]
     pub is_acting_as_team_owner: bool,
@@ -89,15 +102,15 @@ pub struct PendingTransaction {
     #[serde(rename = "memberId")]
     pub member_id: MemberId,
     #[serde(rename = "processDate")]
-    pub process_date: u128,
+    pub process_date: Timestamp,
     #[serde(rename = "proposedDate")]
-    pub proposed_date: u128,
+    pub proposed_date: Timestamp,
     pub rating: u32,
     #[serde(rename = "scoringPeriodId")]
     pub scoring_period_id: u8,
     #[serde(rename = "skipTransactionCounters")]
     pub skip_transaction_counters: bool,
-    pub status: String,
+    pub status: TransactionStatus,
     #[serde(rename = "subOrder")]
     pub sub_order: u16,
     #[serde(rename = "teamActions")]
@@ -130,10 +143,10 @@ pub struct TransactionItem {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RecordLocales {
-    away: Record,
-    division: Record,
-    home: Record,
-    overall: Record,
+    pub away: Record,
+    pub division: Record,
+    pub home: Record,
+    pub overall: Record,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Record {
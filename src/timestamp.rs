@@ -0,0 +1,115 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A moment in time as ESPN sends it: milliseconds since the Unix epoch.
+///
+/// Displays as the raw milliseconds. With the `chrono` feature enabled, converts to and from
+/// `chrono::DateTime<Utc>` and formats as RFC 3339 with `to_rfc3339`. Timestamps outside the range
+/// chrono can represent don't convert.
+#[derive(
+    Debug, Deserialize, Serialize, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[serde(transparent)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn from_millis(millis: i64) -> Timestamp {
+        Timestamp(millis)
+    }
+    pub fn millis(&self) -> i64 {
+        self.0
+    }
+    pub fn now() -> Timestamp {
+        SystemTime::now().into()
+    }
+    /// ESPN sends 0 for dates that were never set.
+    pub fn is_set(&self) -> bool {
+        self.0 != 0
+    }
+    /// `None` when the timestamp is outside the range chrono can represent.
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        (*self).try_into().ok()
+    }
+    #[cfg(feature = "chrono")]
+    pub fn to_rfc3339(&self) -> Option<String> {
+        self.to_datetime().map(|t| t.to_rfc3339())
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => Timestamp(since.as_millis() as i64),
+            Err(e) => Timestamp(-(e.duration().as_millis() as i64)),
+        }
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(timestamp: Timestamp) -> Self {
+        let offset = Duration::from_millis(timestamp.0.unsigned_abs());
+        if timestamp.0 >= 0 {
+            UNIX_EPOCH + offset
+        } else {
+            UNIX_EPOCH - offset
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp(time.timestamp_millis())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = String;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+        chrono::DateTime::from_timestamp_millis(timestamp.0)
+            .ok_or(format!("Timestamp {} is out of range", timestamp.0))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn converts_system_time() {
+        let timestamp = Timestamp(1_694_000_000_123);
+        let time: SystemTime = timestamp.into();
+        assert_eq!(Timestamp::from(time), timestamp);
+        assert_eq!(
+            serde_json::from_str::<Timestamp>("1694000000123").unwrap(),
+            timestamp
+        );
+        assert_eq!(timestamp.to_string(), "1694000000123");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn converts_chrono() {
+        let timestamp = Timestamp(1_694_000_000_123);
+        assert_eq!(Timestamp::from(timestamp.to_datetime().unwrap()), timestamp);
+        assert_eq!(
+            timestamp.to_rfc3339().unwrap(),
+            "2023-09-06T11:33:20.123+00:00"
+        );
+        assert_eq!(Timestamp(i64::MAX).to_datetime(), None);
+        assert!(chrono::DateTime::<chrono::Utc>::try_from(Timestamp(i64::MIN)).is_err());
+    }
+}
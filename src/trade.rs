@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    id_maps::ProTeamId,
+    league::{LeagueSettings, RosterSettings, ScheduleSettings},
+    lineup::{optimal_lineup, LineupCandidate},
+    matchup::Matchup,
+    player::{Player, PlayerId},
+    simulation::{PlayoffSimulation, RemainingGame},
+    team::{Team, TeamId},
+};
+
+/// A proposed exchange of players between two teams.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Trade {
+    pub team_a: TeamId,
+    pub team_b: TeamId,
+    /// Players moving from `team_a` to `team_b`.
+    pub from_a: Vec<PlayerId>,
+    /// Players moving from `team_b` to `team_a`.
+    pub from_b: Vec<PlayerId>,
+}
+
+/// Projected starting lineup points for one scoring period, with and without the trade.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WeeklyProjection {
+    pub scoring_period_id: u8,
+    pub before: f32,
+    pub after: f32,
+}
+
+/// How a trade changes one team's outlook for the rest of the season.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TradeImpact {
+    pub team: TeamId,
+    pub weekly: Vec<WeeklyProjection>,
+    pub playoff_odds_before: f32,
+    pub playoff_odds_after: f32,
+}
+
+impl TradeImpact {
+    /// Change in projected starting lineup points over the rest of the season.
+    pub fn points_change(&self) -> f32 {
+        self.weekly.iter().map(|w| w.after - w.before).sum()
+    }
+    pub fn average_weekly_change(&self) -> f32 {
+        if self.weekly.is_empty() {
+            0.0
+        } else {
+            self.points_change() / self.weekly.len() as f32
+        }
+    }
+    pub fn playoff_odds_change(&self) -> f32 {
+        self.playoff_odds_after - self.playoff_odds_before
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TradeAnalysis {
+    pub team_a: TradeImpact,
+    pub team_b: TradeImpact,
+}
+
+/// Estimates the rest-of-season effect of trades using ESPN's player projections.
+///
+/// Each team's points for a scoring period are those of its optimal lineup, using the
+/// player's projection for that period when ESPN supplied one and their season average
+/// projection otherwise. Players whose NFL team is on bye project no points.
+pub struct TradeAnalyzer<'a> {
+    roster_settings: &'a RosterSettings,
+    schedule_settings: &'a ScheduleSettings,
+    season: u16,
+    teams: &'a [Team],
+    schedule: &'a [Matchup],
+    current_matchup_period: u8,
+    /// Each NFL team's bye week; see `ProTeam::bye_week`. Teams left out are never on bye.
+    pub bye_weeks: HashMap<ProTeamId, u8>,
+    pub simulation: PlayoffSimulation,
+}
+
+impl<'a> TradeAnalyzer<'a> {
+    /// # Arguments
+    ///
+    /// * teams - Teams with rosters, as returned by `get_teams_at_week`.
+    /// * schedule - The season's matchups, as returned by `get_matchups`.
+    /// * current_matchup_period - The first matchup period still to be decided.
+    pub fn new(
+        settings: &'a LeagueSettings,
+        season: u16,
+        teams: &'a [Team],
+        schedule: &'a [Matchup],
+        current_matchup_period: u8,
+    ) -> TradeAnalyzer<'a> {
+        TradeAnalyzer {
+            roster_settings: &settings.roster_settings,
+            schedule_settings: &settings.schedule_settings,
+            season,
+            teams,
+            schedule,
+            current_matchup_period,
+            bye_weeks: HashMap::new(),
+            simulation: PlayoffSimulation::new(settings.schedule_settings.playoff_team_count),
+        }
+    }
+
+    pub fn analyze(&self, trade: &Trade) -> Result<TradeAnalysis, String> {
+        let before = self.rosters();
+        let mut after = before.clone();
+        move_players(&mut after, trade.team_a, trade.team_b, &trade.from_a)?;
+        move_players(&mut after, trade.team_b, trade.team_a, &trade.from_b)?;
+
+        let periods = self.remaining_matchup_periods();
+        let scoring_periods = periods
+            .iter()
+            .flat_map(|mp| self.scoring_periods(*mp))
+            .collect::<Vec<_>>();
+        let weekly = |team: TeamId| {
+            scoring_periods
+                .iter()
+                .map(|sp| WeeklyProjection {
+                    scoring_period_id: *sp,
+                    before: self.projected_points(&before, team, *sp),
+                    after: self.projected_points(&after, team, *sp),
+                })
+                .collect::<Vec<_>>()
+        };
+        let odds_before = self
+            .simulation
+            .playoff_odds(self.teams, &self.remaining_games(&before, &periods));
+        let odds_after = self
+            .simulation
+            .playoff_odds(self.teams, &self.remaining_games(&after, &periods));
+        let impact = |team: TeamId| TradeImpact {
+            team,
+            weekly: weekly(team),
+            playoff_odds_before: odds_before.get(&team).copied().unwrap_or_default(),
+            playoff_odds_after: odds_after.get(&team).copied().unwrap_or_default(),
+        };
+        Ok(TradeAnalysis {
+            team_a: impact(trade.team_a),
+            team_b: impact(trade.team_b),
+        })
+    }
+
+    fn rosters(&self) -> HashMap<TeamId, Vec<Player>> {
+        self.teams
+            .iter()
+            .map(|t| {
+                let players = t
+                    .roster
+                    .as_ref()
+                    .map(|r| {
+                        r.entries
+                            .iter()
+                            .map(|e| e.player_pool_entry.player.clone())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (t.id, players)
+            })
+            .collect()
+    }
+
+    fn remaining_matchup_periods(&self) -> Vec<u8> {
+        (self.current_matchup_period..=self.schedule_settings.matchup_period_count).collect()
+    }
+
+    fn scoring_periods(&self, matchup_period: u8) -> Vec<u8> {
+        match self.schedule_settings.matchup_periods.get(&matchup_period) {
+            Some(periods) => periods.clone(),
+            None => vec![matchup_period],
+        }
+    }
+
+    fn projected_points(
+        &self,
+        rosters: &HashMap<TeamId, Vec<Player>>,
+        team: TeamId,
+        scoring_period_id: u8,
+    ) -> f32 {
+        let candidates = rosters
+            .get(&team)
+            .map(|players| {
+                players
+                    .iter()
+                    .map(|p| LineupCandidate {
+                        player_id: p.id,
+                        eligible_slots: p.eligible_slots.clone(),
+                        points: if self.bye_weeks.get(&p.pro_team_id) == Some(&scoring_period_id) {
+                            0.0
+                        } else {
+                            p.projected_points(self.season, scoring_period_id)
+                                .or_else(|| p.projected_average(self.season))
+                                .unwrap_or_default()
+                        },
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        optimal_lineup(self.roster_settings, &candidates).points
+    }
+
+    fn remaining_games(
+        &self,
+        rosters: &HashMap<TeamId, Vec<Player>>,
+        periods: &[u8],
+    ) -> Vec<RemainingGame> {
+        self.schedule
+            .iter()
+            .filter(|m| !m.winner.is_decided() && periods.contains(&m.matchup_period_id))
+            .filter_map(|m| {
+                let home = m.home.as_ref()?.team_id;
+                let away = m.away.as_ref()?.team_id;
+                let projection = |team: TeamId| {
+                    self.scoring_periods(m.matchup_period_id)
+                        .iter()
+                        .map(|sp| self.projected_points(rosters, team, *sp))
+                        .sum()
+                };
+                Some(RemainingGame {
+                    home,
+                    away,
+                    home_projection: projection(home),
+                    away_projection: projection(away),
+                })
+            })
+            .collect()
+    }
+}
+
+fn move_players(
+    rosters: &mut HashMap<TeamId, Vec<Player>>,
+    from: TeamId,
+    to: TeamId,
+    players: &[PlayerId],
+) -> Result<(), String> {
+    for player_id in players {
+        let source = rosters
+            .get_mut(&from)
+            .ok_or(format!("No roster for team {}", from))?;
+        let index = source
+            .iter()
+            .position(|p| &p.id == player_id)
+            .ok_or(format!("Player {:?} is not on team {}", player_id, from))?;
+        let player = source.remove(index);
+        rosters.entry(to).or_default().push(player);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::synthetic::{LeagueGenerator, SyntheticLeague};
+
+    fn league() -> SyntheticLeague {
+        let mut generator = LeagueGenerator::new(5);
+        generator.weeks_played = Some(10);
+        generator.generate()
+    }
+
+    /// The team's best player by projected average, who starts every week.
+    fn star(team: &Team) -> PlayerId {
+        team.roster
+            .as_ref()
+            .unwrap()
+            .entries
+            .iter()
+            .map(|e| &e.player_pool_entry.player)
+            .max_by(|a, b| {
+                let average = |p: &Player| p.projected_average(2023).unwrap_or_default();
+                average(a).total_cmp(&average(b))
+            })
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn giving_away_a_starter_moves_points_to_the_other_team() {
+        let league = league();
+        let analyzer = TradeAnalyzer::new(
+            &league.settings,
+            league.season,
+            &league.teams,
+            &league.schedule,
+            11,
+        );
+        let (a, b) = (&league.teams[0], &league.teams[1]);
+        let trade = Trade {
+            team_a: a.id,
+            team_b: b.id,
+            from_a: vec![star(a)],
+            from_b: vec![],
+        };
+        let analysis = analyzer.analyze(&trade).unwrap();
+        // Weeks 11 through 14 of the regular season are left.
+        assert_eq!(analysis.team_a.weekly.len(), 4);
+        assert_eq!(analysis.team_a.weekly[0].scoring_period_id, 11);
+        assert!(analysis.team_a.points_change() < 0.0);
+        assert!(analysis.team_b.points_change() >= 0.0);
+        assert!(analysis.team_a.playoff_odds_change() <= 0.0);
+    }
+
+    #[test]
+    fn rejects_players_not_on_the_team() {
+        let league = league();
+        let analyzer = TradeAnalyzer::new(
+            &league.settings,
+            league.season,
+            &league.teams,
+            &league.schedule,
+            11,
+        );
+        let (a, b) = (&league.teams[0], &league.teams[1]);
+        let trade = Trade {
+            team_a: a.id,
+            team_b: b.id,
+            from_a: vec![star(b)],
+            from_b: vec![],
+        };
+        assert!(analyzer.analyze(&trade).is_err());
+    }
+
+    #[test]
+    fn players_on_bye_project_no_points() {
+        let league = league();
+        let team = &league.teams[0];
+        let trade = Trade {
+            team_a: team.id,
+            team_b: league.teams[1].id,
+            from_a: vec![],
+            from_b: vec![],
+        };
+        let mut analyzer = TradeAnalyzer::new(
+            &league.settings,
+            league.season,
+            &league.teams,
+            &league.schedule,
+            11,
+        );
+        let week_12 = |analysis: TradeAnalysis| {
+            analysis
+                .team_a
+                .weekly
+                .iter()
+                .find(|w| w.scoring_period_id == 12)
+                .unwrap()
+                .before
+        };
+        assert!(week_12(analyzer.analyze(&trade).unwrap()) > 0.0);
+
+        analyzer.bye_weeks = team
+            .roster
+            .as_ref()
+            .unwrap()
+            .entries
+            .iter()
+            .map(|e| (e.player_pool_entry.player.pro_team_id.clone(), 12))
+            .collect();
+        let analysis = analyzer.analyze(&trade).unwrap();
+        assert_eq!(week_12(analysis.clone()), 0.0);
+        assert!(analysis.team_a.weekly.iter().any(|w| w.before > 0.0));
+    }
+}
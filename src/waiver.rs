@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    free_agent::{FreeAgent, PositionalRatings},
+    id_maps::ProTeamId,
+    league::AcquisitionSettings,
+    player::{Player, PlayerId},
+    position::Position,
+    team::Team,
+};
+
+/// A suggested waiver claim: pick up `add`, releasing `drop` to make room.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WaiverRecommendation {
+    pub add: PlayerId,
+    pub add_name: String,
+    pub drop: Option<PlayerId>,
+    pub drop_name: Option<String>,
+    pub position: Position,
+    /// ESPN's positional ranking of the free agent, if rated.
+    pub positional_ranking: Option<u32>,
+    /// Matchup-adjusted value of the free agent for the scoring period.
+    pub value: f32,
+    /// How much better the free agent is than the player dropped.
+    pub gain: f32,
+    /// Only suggested when the league uses a free agent acquisition budget.
+    pub suggested_bid: Option<f32>,
+}
+
+/// Ranks available free agents against a team's roster for an upcoming scoring period.
+///
+/// A player's value is their projection for the scoring period, scaled by how many points their
+/// opponent allows to the position relative to the league average, plus a small bonus for
+/// players whose ownership is rising. Free agents and rostered players are valued the same way.
+///
+/// Players whose NFL team is on bye are left out on both sides: they score nothing this scoring
+/// period, which says nothing about their worth after it. A free agent is only suggested without
+/// a drop when the roster has an open spot.
+pub struct WaiverRecommender<'a> {
+    acquisition: &'a AcquisitionSettings,
+    roster_size: Option<usize>,
+    free_agents: &'a [FreeAgent],
+    ratings: &'a PositionalRatings,
+    opponents: &'a HashMap<ProTeamId, ProTeamId>,
+    season: u16,
+    scoring_period_id: u8,
+    /// Points of value per percentage point of ownership change.
+    pub trend_weight: f32,
+    /// The largest share of the remaining budget to suggest bidding on a single player.
+    pub max_bid_fraction: f32,
+}
+
+impl<'a> WaiverRecommender<'a> {
+    /// # Arguments
+    ///
+    /// * roster_size - How many players a team can roster outside injured reserve, `None` if
+    ///   unlimited; see `RosterSettings::roster_size`.
+    /// * opponents - Each NFL team's opponent for the scoring period; see `pro_team::opponents_for_week`.
+    pub fn new(
+        acquisition: &'a AcquisitionSettings,
+        roster_size: Option<usize>,
+        free_agents: &'a [FreeAgent],
+        ratings: &'a PositionalRatings,
+        opponents: &'a HashMap<ProTeamId, ProTeamId>,
+        season: u16,
+        scoring_period_id: u8,
+    ) -> WaiverRecommender<'a> {
+        WaiverRecommender {
+            acquisition,
+            roster_size,
+            free_agents,
+            ratings,
+            opponents,
+            season,
+            scoring_period_id,
+            trend_weight: 0.05,
+            max_bid_fraction: 0.25,
+        }
+    }
+
+    /// Add/drop suggestions for the team, best first. The team must include its roster.
+    pub fn recommend(&self, team: &Team) -> Vec<WaiverRecommendation> {
+        let active = team
+            .roster
+            .iter()
+            .flat_map(|r| &r.entries)
+            .filter(|e| !e.lineup_slot_id.is_injured_reserve())
+            .map(|e| &e.player_pool_entry.player)
+            .collect::<Vec<_>>();
+        let has_open_spot = self.roster_size.is_none_or(|size| active.len() < size);
+        let roster = active
+            .into_iter()
+            .filter(|p| p.droppable)
+            .filter_map(|p| Some((p, self.value(p)?)))
+            .collect::<Vec<_>>();
+        let remaining_budget =
+            self.acquisition.acquisition_budget - team.transaction_counter.acquisition_budget_spent;
+
+        let mut recommendations = self
+            .free_agents
+            .iter()
+            .filter_map(|fa| {
+                let value = self.value(&fa.player)?;
+                let drop = roster
+                    .iter()
+                    .filter(|(p, _)| p.default_position_id == fa.player.default_position_id)
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .or_else(|| roster.iter().min_by(|a, b| a.1.total_cmp(&b.1)));
+                if drop.is_none() && !has_open_spot {
+                    return None;
+                }
+                let gain = value - drop.map(|d| d.1).unwrap_or_default();
+                if gain <= 0.0 {
+                    return None;
+                }
+                Some(WaiverRecommendation {
+                    add: fa.id,
+                    add_name: fa.player.full_name.clone(),
+                    drop: drop.map(|d| d.0.id),
+                    drop_name: drop.map(|d| d.0.full_name.clone()),
+                    position: fa.player.default_position_id,
+                    positional_ranking: fa.ratings.get(&0).map(|r| r.positional_ranking),
+                    value,
+                    gain,
+                    suggested_bid: self.suggested_bid(gain, value, remaining_budget),
+                })
+            })
+            .collect::<Vec<_>>();
+        recommendations.sort_by(|a, b| b.gain.total_cmp(&a.gain));
+        recommendations
+    }
+
+    /// The player's value for the scoring period, or `None` if their NFL team is on bye.
+    fn value(&self, player: &Player) -> Option<f32> {
+        let opponent = self.opponents.get(&player.pro_team_id)?;
+        let projection = player
+            .projected_points(self.season, self.scoring_period_id)
+            .or_else(|| player.projected_average(self.season))
+            .unwrap_or_default();
+        let factor = self
+            .ratings
+            .positional_ratings
+            .get(&player.default_position_id)
+            .and_then(|r| {
+                let against = r.rating_by_opponent.get(opponent)?;
+                (r.average > 0.0).then(|| against.average / r.average)
+            })
+            .unwrap_or(1.0);
+        Some(projection * factor + self.trend(player))
+    }
+
+    fn trend(&self, player: &Player) -> f32 {
+        player
+            .ownership
+            .as_ref()
+            .map(|o| o.percent_change * self.trend_weight)
+            .unwrap_or_default()
+    }
+
+    fn suggested_bid(&self, gain: f32, value: f32, remaining_budget: f32) -> Option<f32> {
+        if !self.acquisition.is_using_acquisition_budget {
+            return None;
+        }
+        let share = if value > 0.0 {
+            (gain / value).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let bid = (remaining_budget * share * self.max_bid_fraction).round();
+        Some(
+            bid.max(self.acquisition.minimum_bid)
+                .min(remaining_budget.max(0.0)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::{
+        free_agent::FreeAgentResponse,
+        league::LeagueResponse,
+        pro_team::{opponents_for_week, ProTeamScheduleResponse},
+        team::TeamResponse,
+    };
+
+    fn fixture<T: DeserializeOwned>(name: &str) -> T {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/espn/ppr/2023")
+            .join(name);
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    struct Week1 {
+        acquisition: AcquisitionSettings,
+        free_agents: FreeAgentResponse,
+        opponents: HashMap<ProTeamId, ProTeamId>,
+        team: Team,
+    }
+
+    impl Week1 {
+        fn load() -> Week1 {
+            let league: LeagueResponse = fixture("mSettings.json");
+            let schedules: ProTeamScheduleResponse = fixture("proTeamSchedules_wl.json");
+            let teams: TeamResponse = fixture("mRoster+mTeam_1.json");
+            Week1 {
+                acquisition: league.settings.unwrap().acquisition_settings,
+                free_agents: fixture("kona_player_info_1.json"),
+                opponents: opponents_for_week(&schedules.settings.pro_teams, 1),
+                team: teams.teams.into_iter().next().unwrap(),
+            }
+        }
+
+        fn recommender(&self) -> WaiverRecommender<'_> {
+            self.recommender_with(None)
+        }
+
+        fn recommender_with(&self, roster_size: Option<usize>) -> WaiverRecommender<'_> {
+            WaiverRecommender::new(
+                &self.acquisition,
+                roster_size,
+                &self.free_agents.players,
+                &self.free_agents.position_against_opponent,
+                &self.opponents,
+                2023,
+                1,
+            )
+        }
+
+        fn roster(&self) -> impl Iterator<Item = &Player> {
+            self.team
+                .roster
+                .as_ref()
+                .unwrap()
+                .entries
+                .iter()
+                .filter(|e| !e.lineup_slot_id.is_injured_reserve())
+                .map(|e| &e.player_pool_entry.player)
+                .filter(|p| p.droppable)
+        }
+    }
+
+    #[test]
+    fn drops_the_weakest_player_at_the_position() {
+        let week = Week1::load();
+        let recommender = week.recommender();
+        let recommendations = recommender.recommend(&week.team);
+        assert!(!recommendations.is_empty());
+        for recommendation in &recommendations {
+            let drop = week
+                .roster()
+                .find(|p| Some(p.id) == recommendation.drop)
+                .unwrap();
+            let drop_value = recommender.value(drop).unwrap();
+            // Both sides include the ownership trend.
+            assert!((recommendation.gain - (recommendation.value - drop_value)).abs() < 1e-3);
+            let same_position = week
+                .roster()
+                .filter(|p| p.default_position_id == recommendation.position)
+                .collect::<Vec<_>>();
+            if !same_position.is_empty() {
+                assert_eq!(drop.default_position_id, recommendation.position);
+                assert!(same_position
+                    .iter()
+                    .all(|p| recommender.value(p).unwrap() >= drop_value));
+            }
+        }
+    }
+
+    #[test]
+    fn never_drops_players_on_bye() {
+        let mut week = Week1::load();
+        let dropped = week.recommender().recommend(&week.team)[0].drop.unwrap();
+        let pro_team = week
+            .roster()
+            .find(|p| p.id == dropped)
+            .unwrap()
+            .pro_team_id
+            .clone();
+        week.opponents.remove(&pro_team);
+        let recommendations = week.recommender().recommend(&week.team);
+        assert!(recommendations.iter().all(|r| r.drop != Some(dropped)));
+        assert!(recommendations.iter().all(|r| {
+            week.roster()
+                .chain(week.free_agents.players.iter().map(|fa| &fa.player))
+                .filter(|p| p.pro_team_id == pro_team)
+                .all(|p| Some(p.id) != r.drop && p.id != r.add)
+        }));
+    }
+
+    #[test]
+    fn only_adds_without_a_drop_when_there_is_room() {
+        let mut week = Week1::load();
+        for entry in &mut week.team.roster.as_mut().unwrap().entries {
+            entry.player_pool_entry.player.droppable = false;
+        }
+        let rostered = week
+            .team
+            .roster
+            .as_ref()
+            .unwrap()
+            .entries
+            .iter()
+            .filter(|e| !e.lineup_slot_id.is_injured_reserve())
+            .count();
+        let full = week.recommender_with(Some(rostered)).recommend(&week.team);
+        assert!(full.is_empty());
+        let room = week
+            .recommender_with(Some(rostered + 1))
+            .recommend(&week.team);
+        assert!(!room.is_empty());
+        assert!(room.iter().all(|r| r.drop.is_none() && r.gain == r.value));
+    }
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    live::{LiveMatchup, LivePlayer, LiveTeam},
+    matchup::MatchupOutcome,
+    pro_team::GameStatus,
+    simulation::normal_cdf,
+    team::TeamId,
+};
+
+/// Standard deviation of a player's points, as a share of their projection, when ESPN doesn't
+/// supply a variance for their projected stats.
+const DEFAULT_RELATIVE_STD_DEV: f32 = 0.45;
+
+/// Each side's chance of winning a matchup from where it stands.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WinProbability {
+    pub home_team: TeamId,
+    pub away_team: TeamId,
+    pub home: f32,
+    pub away: f32,
+    /// Points scored so far plus what the remaining starters are projected to add.
+    pub home_projected: f32,
+    pub away_projected: f32,
+}
+
+impl WinProbability {
+    pub fn of(&self, team: TeamId) -> Option<f32> {
+        if team == self.home_team {
+            Some(self.home)
+        } else if team == self.away_team {
+            Some(self.away)
+        } else {
+            None
+        }
+    }
+}
+
+/// Estimates each side's chance of winning, treating the points still to come from each starter
+/// as normally distributed around their remaining projection. Returns `None` for a bye.
+///
+/// Players mid-game are expected to add whatever of their projection they haven't scored yet,
+/// with variance shrunk by the same share. In a matchup spanning several scoring periods, the
+/// current starters are also expected to score their projections for the periods still to come.
+/// Once ESPN has decided the matchup, its result stands.
+pub fn win_probability(matchup: &LiveMatchup) -> Option<WinProbability> {
+    let away = matchup.away.as_ref()?;
+    let (home_mean, home_variance) = remaining(&matchup.home);
+    let (away_mean, away_variance) = remaining(away);
+    let home_projected = matchup.home.points + home_mean;
+    let away_projected = away.points + away_mean;
+    let difference = home_projected - away_projected;
+    let std_dev = (home_variance + away_variance).sqrt();
+    let home = match matchup.winner {
+        MatchupOutcome::Home => 1.0,
+        MatchupOutcome::Away => 0.0,
+        MatchupOutcome::Tie => 0.5,
+        _ if matchup.winner.is_decided() || std_dev <= f32::EPSILON => match difference {
+            d if d > 0.0 => 1.0,
+            d if d < 0.0 => 0.0,
+            _ => 0.5,
+        },
+        _ => normal_cdf(difference / std_dev),
+    };
+    Some(WinProbability {
+        home_team: matchup.home.team,
+        away_team: away.team,
+        home,
+        away: 1.0 - home,
+        home_projected,
+        away_projected,
+    })
+}
+
+impl LiveMatchup {
+    pub fn win_probability(&self) -> Option<WinProbability> {
+        win_probability(self)
+    }
+}
+
+/// Expected points still to come from a team's starters, and the variance of that total.
+fn remaining(team: &LiveTeam) -> (f32, f32) {
+    let later = team
+        .starters()
+        .flat_map(|p| &p.later_projections)
+        .map(|projected| {
+            let projected = projected.max(0.0);
+            (projected, (projected * DEFAULT_RELATIVE_STD_DEV).powi(2))
+        });
+    team.remaining()
+        .map(player_remaining)
+        .chain(later)
+        .fold((0.0, 0.0), |(mean, variance), (m, v)| {
+            (mean + m, variance + v)
+        })
+}
+
+fn player_remaining(player: &LivePlayer) -> (f32, f32) {
+    let projected = player.projected_points().unwrap_or_default().max(0.0);
+    if projected <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let mean = match player.game {
+        GameStatus::NotStarted => projected,
+        GameStatus::InProgress => (projected - player.points).max(0.0),
+        GameStatus::Unofficial | GameStatus::Final | GameStatus::Bye => 0.0,
+    };
+    let share = mean / projected;
+    let variance =
+        projected_variance(player).unwrap_or((projected * DEFAULT_RELATIVE_STD_DEV).powi(2));
+    (mean, variance * share)
+}
+
+/// The variance of a player's projected fantasy points, from the variance of each projected stat
+/// scaled by what the league awards per unit of that stat.
+fn projected_variance(player: &LivePlayer) -> Option<f32> {
+    let projection = player.projection.as_ref()?;
+    let variances = projection.variance.as_ref()?;
+    let applied = projection.applied_stats.as_ref()?;
+    let variance = applied
+        .iter()
+        .filter_map(|(stat, points)| {
+            let id = u16::try_from(stat.0).ok()?;
+            let value = *projection.stats.get(&id)?;
+            let variance = *variances.get(&id)?;
+            (value.abs() > f32::EPSILON).then(|| (points / value).powi(2) * variance)
+        })
+        .sum::<f32>();
+    (variance > 0.0).then_some(variance)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        id_maps::ProTeamId,
+        player::{PlayerId, Stats},
+        position::LineupSlot,
+    };
+
+    fn player(points: f32, projected: f32, game: GameStatus) -> LivePlayer {
+        LivePlayer {
+            player_id: PlayerId(1),
+            name: "Player".to_string(),
+            pro_team: ProTeamId(1),
+            slot: LineupSlot::RunningBack,
+            points,
+            projection: Some(Stats {
+                applied_total: projected,
+                ..Default::default()
+            }),
+            later_projections: vec![],
+            game,
+        }
+    }
+
+    fn matchup(home: Vec<LivePlayer>, away: Vec<LivePlayer>) -> LiveMatchup {
+        let team = |id, players: Vec<LivePlayer>| LiveTeam {
+            team: TeamId(id),
+            points: players.iter().map(|p| p.points).sum(),
+            players,
+        };
+        LiveMatchup {
+            matchup_id: 1,
+            scoring_period_id: 1,
+            last_scoring_period_id: 1,
+            winner: MatchupOutcome::Undecided,
+            home: team(1, home),
+            away: Some(team(2, away)),
+        }
+    }
+
+    #[test]
+    fn leads_grow_safer_as_players_finish() {
+        let even = matchup(
+            vec![player(0.0, 15.0, GameStatus::NotStarted)],
+            vec![player(0.0, 15.0, GameStatus::NotStarted)],
+        );
+        let probability = win_probability(&even).unwrap();
+        assert!((probability.home - 0.5).abs() < 1e-3);
+
+        let ahead = matchup(
+            vec![player(20.0, 15.0, GameStatus::Final)],
+            vec![player(0.0, 15.0, GameStatus::NotStarted)],
+        );
+        let probability = win_probability(&ahead).unwrap();
+        assert!(probability.home > 0.7 && probability.home < 1.0);
+        assert_eq!(probability.of(TeamId(2)), Some(probability.away));
+
+        let done = matchup(
+            vec![player(20.0, 15.0, GameStatus::Final)],
+            vec![player(12.0, 15.0, GameStatus::Final)],
+        );
+        assert_eq!(win_probability(&done).unwrap().home, 1.0);
+    }
+
+    #[test]
+    fn counts_the_rest_of_a_multi_week_matchup() {
+        let mut first_week = matchup(
+            vec![player(20.0, 15.0, GameStatus::Final)],
+            vec![player(12.0, 15.0, GameStatus::Final)],
+        );
+        assert_eq!(win_probability(&first_week).unwrap().home, 1.0);
+
+        first_week.last_scoring_period_id = 2;
+        first_week.home.players[0].later_projections = vec![10.0];
+        first_week.away.as_mut().unwrap().players[0].later_projections = vec![25.0];
+        let probability = win_probability(&first_week).unwrap();
+        assert_eq!(probability.home_projected, 30.0);
+        assert_eq!(probability.away_projected, 37.0);
+        assert!(probability.home > 0.0 && probability.home < 0.5);
+    }
+
+    #[test]
+    fn decided_matchups_take_espns_result() {
+        let mut tiebreak = matchup(
+            vec![player(20.0, 15.0, GameStatus::Final)],
+            vec![player(20.0, 15.0, GameStatus::Final)],
+        );
+        tiebreak.winner = MatchupOutcome::Away;
+        let probability = win_probability(&tiebreak).unwrap();
+        assert_eq!(probability.home, 0.0);
+        assert_eq!(probability.away, 1.0);
+    }
+}
@@ -0,0 +1,294 @@
+//! Deserializes every response in `tests/fixtures/espn` into the struct the client would use for
+//! it, reporting the exact path of any field that no longer matches.
+
+use std::path::{Path, PathBuf};
+
+use espn_fantasy_football::{
+    client::EspnClient,
+    free_agent::FreeAgentResponse,
+    id_maps::StatId,
+    league::{LeagueResponse, PlayerRankType},
+    matchup::{MatchupKind, MatchupOutcome, MatchupResponse},
+    player::StatSource,
+    position::{LineupSlot, Position},
+    pro_team::ProTeamScheduleResponse,
+    status::{InjuryStatus, RosterStatus},
+    team::{TeamId, TeamResponse},
+};
+use serde::de::DeserializeOwned;
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/espn");
+
+fn corpus() -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::from(CORPUS)];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|e| e == "json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn parse<T: DeserializeOwned>(path: &Path) -> T {
+    let json = std::fs::read_to_string(path).unwrap();
+    let de = &mut serde_json::Deserializer::from_str(&json);
+    serde_path_to_error::deserialize(de).unwrap_or_else(|e| {
+        panic!(
+            "{} does not match at `{}`: {}",
+            path.display(),
+            e.path(),
+            e.inner()
+        )
+    })
+}
+
+/// The views a saved response was requested with, from its `FileSource` file name.
+fn views(path: &Path) -> String {
+    let stem = path.file_stem().unwrap().to_str().unwrap();
+    match stem.rsplit_once('_') {
+        Some((views, period)) if period.parse::<u8>().is_ok() => views.to_string(),
+        _ => stem.to_string(),
+    }
+}
+
+fn client(league_type: &str) -> EspnClient {
+    EspnClient::offline(1, Path::new(CORPUS).join(league_type))
+}
+
+#[test]
+fn every_response_deserializes() {
+    let files = corpus();
+    assert!(
+        files.len() >= 50,
+        "Corpus is missing, found {}",
+        files.len()
+    );
+    for path in &files {
+        match views(path).as_str() {
+            "mSettings" => assert!(parse::<LeagueResponse>(path).settings.is_some()),
+            "mStatus" => assert!(parse::<LeagueResponse>(path).status.is_some()),
+            "mTeam" | "mRoster+mTeam" => assert!(!parse::<TeamResponse>(path).teams.is_empty()),
+            "mMatchup" | "mMatchup+mMatchupScore" => {
+                assert!(!parse::<MatchupResponse>(path).schedule.is_empty())
+            }
+            "kona_player_info" => assert!(!parse::<FreeAgentResponse>(path).players.is_empty()),
+            "proTeamSchedules_wl" => {
+                assert!(!parse::<ProTeamScheduleResponse>(path)
+                    .settings
+                    .pro_teams
+                    .is_empty())
+            }
+            other => panic!(
+                "{} was requested with views `{}`, which no test knows how to read",
+                path.display(),
+                other
+            ),
+        }
+    }
+}
+
+#[tokio::test]
+async fn standard_league() {
+    let client = client("standard");
+    let settings = client.get_league_settings(2019).await;
+    assert_eq!(
+        settings.scoring_settings.player_rank_type,
+        PlayerRankType::Standard
+    );
+    assert!(!settings
+        .scoring_settings
+        .scoring_items
+        .iter()
+        .any(|i| i.stat_id == StatId(53)));
+    assert_eq!(settings.schedule_settings.matchup_period_count, 14);
+    assert_eq!(settings.draft_settings.draft_type, "SNAKE");
+
+    let status = client.get_league_status(2020).await;
+    assert_eq!(status.previous_seasons, Some(vec![2018, 2019]));
+    assert!(status.waiver_last_executed().is_some());
+
+    let teams = client.get_teams_at_week(2019, 1).await;
+    assert_eq!(teams.len(), 4);
+    let roster = teams[0].roster.as_ref().unwrap();
+    assert_eq!(roster.entries.len(), 16);
+    assert_eq!(
+        roster
+            .entries
+            .iter()
+            .filter(|e| e.lineup_slot_id.is_starter())
+            .count(),
+        9
+    );
+    let player = &roster.entries[0].player_pool_entry.player;
+    assert_eq!(player.default_position_id, Position::Quarterback);
+    assert!(player.projected_points(2019, 1).is_some());
+    assert!(player.stat_line(StatSource::Actual, 2019, None).is_some());
+    assert_eq!(
+        roster.entries[0].player_pool_entry.status,
+        RosterStatus::OnTeam
+    );
+}
+
+#[tokio::test]
+async fn ppr_league() {
+    let client = client("ppr");
+    let settings = client.get_league_settings(2023).await;
+    assert!(settings.scoring_settings.player_rank_type.is_ppr());
+    let receptions = settings
+        .scoring_settings
+        .scoring_items
+        .iter()
+        .find(|i| i.stat_id == StatId(53))
+        .unwrap();
+    assert_eq!(receptions.points, 1.0);
+    assert!(settings.acquisition_settings.is_using_acquisition_budget);
+
+    let matchups = client.get_matchups_for_week(2023, 1).await;
+    assert_eq!(matchups.len(), 2);
+    for matchup in &matchups {
+        assert_eq!(matchup.kind(), MatchupKind::Regular);
+        assert!(matchup.winner.is_decided());
+        for side in matchup.teams() {
+            assert!(side.roster_for_current_scoring_period.is_some());
+            assert_eq!(side.total_points_live, Some(side.total_points));
+        }
+    }
+
+    let season = client.get_matchups(2022).await;
+    assert!(season
+        .iter()
+        .any(|m| m.kind() == MatchupKind::Playoff && m.winner == MatchupOutcome::Undecided));
+    assert!(season.iter().any(|m| m.kind() == MatchupKind::Bye));
+
+    let teams = client.get_team_data(2023).await;
+    let pending = teams[0].pending_transactions.as_ref().unwrap();
+    assert_eq!(pending[0].items.len(), 2);
+    assert_eq!(pending[0].bid_amount, 7);
+
+    let free_agents = client.get_free_agents_for_week(2023, 1, 50).await;
+    assert_eq!(free_agents.len(), 10);
+    assert!(free_agents
+        .iter()
+        .all(|p| matches!(p.status, RosterStatus::FreeAgent | RosterStatus::Waivers)));
+
+    let schedules = client.get_pro_team_schedules(2023).await;
+    let team = schedules.iter().find(|t| t.id.0 == 1).unwrap();
+    assert!(team.game(1).is_some());
+    assert!(team.opponent(1).is_some());
+}
+
+#[tokio::test]
+async fn auction_league() {
+    let client = client("auction");
+    let settings = client.get_league_settings(2022).await;
+    assert_eq!(settings.draft_settings.draft_type, "AUCTION");
+    assert_eq!(settings.draft_settings.auction_budget, 200.0);
+    let teams = client.get_team_data(2022).await;
+    assert!(teams
+        .iter()
+        .all(|t| t.transaction_counter.acquisition_budget_spent >= 0.0));
+}
+
+#[tokio::test]
+async fn keeper_league() {
+    let client = client("keeper");
+    for season in [2021, 2022] {
+        let settings = client.get_league_settings(season).await;
+        assert_eq!(settings.draft_settings.keeper_count, 3);
+        let teams = client.get_team_data(season).await;
+        let keepers = teams[0]
+            .draft_strategy
+            .as_ref()
+            .and_then(|s| s.keeper_player_ids.as_ref())
+            .unwrap();
+        assert_eq!(keepers.len(), 3);
+    }
+}
+
+#[tokio::test]
+async fn history_reports_missing_seasons() {
+    let client = client("keeper");
+    // The corpus has no 2020 season, so fetches fail rather than panic.
+    let error = client.get_season_snapshot(2020).await.err().unwrap();
+    assert_eq!(error, "404");
+    assert!(client.get_league_history(2020).await.is_err());
+}
+
+#[tokio::test]
+async fn idp_league() {
+    let client = client("idp");
+    let settings = client.get_league_settings(2023).await;
+    let slots = &settings.roster_settings.lineup_slot_counts;
+    assert_eq!(slots.get(&LineupSlot::Linebacker), Some(&2));
+    assert_eq!(slots.get(&LineupSlot::DefensiveBack), Some(&2));
+    assert_eq!(slots.get(&LineupSlot::TeamDefense), Some(&0));
+    assert!(settings
+        .scoring_settings
+        .scoring_items
+        .iter()
+        .any(|i| i.stat_id == StatId(108)));
+
+    let teams = client.get_teams_at_week(2023, 1).await;
+    let defenders: Vec<_> = teams[0]
+        .roster
+        .as_ref()
+        .unwrap()
+        .entries
+        .iter()
+        .map(|e| &e.player_pool_entry.player)
+        .filter(|p| p.default_position_id.is_idp())
+        .collect();
+    assert!(!defenders.is_empty());
+    for player in defenders {
+        let line = player.stat_line(StatSource::Actual, 2023, Some(1)).unwrap();
+        assert!(line.stats.contains_key(&109));
+        assert!(!matches!(
+            player.injury_status,
+            Some(InjuryStatus::Other(_))
+        ));
+    }
+    assert_eq!(teams[0].id, TeamId(1));
+}
+
+#[tokio::test]
+async fn schema_reports_cover_corpus() {
+    // The corpus is synthetic, so this only checks that every view is reported on; which fields
+    // ESPN adds is for `check_schema` against a real league to say.
+    let reports = client("ppr").check_schema(2023, 1).await;
+    assert_eq!(reports.len(), 9);
+    for report in reports.iter().filter(|r| !r.view.contains("mBoxscore")) {
+        assert!(report.error.is_none(), "{}", report);
+        assert!(report.missing.is_empty(), "{}", report);
+    }
+    let unsaved = reports
+        .iter()
+        .find(|r| r.view.contains("mBoxscore"))
+        .unwrap();
+    assert!(unsaved.error.is_some());
+}
+
+#[tokio::test]
+async fn raw_json_reaches_unmodeled_fields() {
+    let client = client("keeper");
+    let request = client.league_request(2022).view("mTeam");
+    let league = client.get_raw::<TeamResponse>(&request).await.unwrap();
+    // Neither `seasonId` nor a team's `waiverRank` is modeled.
+    assert_eq!(league.get::<u16>("/seasonId"), Some(2022));
+    let team = league.team(TeamId(2)).unwrap();
+    assert_eq!(team.get::<u8>("/waiverRank"), Some(2));
+    assert_eq!(team.id, TeamId(2));
+    assert_eq!(serde_json::to_value(&league).unwrap(), *league.json());
+
+    let request = client.free_agent_request(2022, 1, 50);
+    let free_agents = client.get_raw::<FreeAgentResponse>(&request).await.unwrap();
+    let first = &free_agents.players[0];
+    let player = free_agents.player(first.id).unwrap();
+    assert!(player.get::<bool>("/rosterLocked").is_some());
+}